@@ -0,0 +1,39 @@
+use serenity::all::{CommandDataOptionValue, CommandInteraction, CreateAutocompleteResponse, CreateInteractionResponseMessage, InteractionResponseFlags};
+use serenity::{async_trait, Error};
+use crate::commands::{find_option, sub_options, todo_choices, AutocompleteTrait, CommandTrait};
+use crate::component::check_subtask_component::checklist_message;
+use crate::database::todo_repo::TodoRepo;
+use crate::global::discord::{Discord, Guild};
+
+pub struct ChecklistTodoCommand;
+
+#[async_trait]
+impl CommandTrait for ChecklistTodoCommand {
+    async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        let options = sub_options(command, "checklist")?;
+        let id = match find_option(options, "todo") {
+            Some(CommandDataOptionValue::Integer(id)) => *id as u64,
+            _ => Err(Error::Other("투두를 선택해주세요"))?
+        };
+
+        // 추가/삭제할 하위 작업이 없으면 체크리스트만 보여줌
+        let todo_repo = TodoRepo::new(Guild::from(discord), discord.database.clone());
+        let mut todo = todo_repo.get_todo(id).await?;
+        if let Some(CommandDataOptionValue::Integer(number)) = find_option(options, "remove") {
+            let index = usize::try_from(*number - 1).map_err(|_| Error::Other("하위 작업을 찾을 수 없습니다"))?;
+            todo = todo_repo.remove_subtask(id, index).await?;
+        }
+        if let Some(CommandDataOptionValue::String(content)) = find_option(options, "add") {
+            todo = todo_repo.add_subtask(id, content.trim()).await?;
+        }
+
+        Ok(Some(checklist_message(&todo).flags(InteractionResponseFlags::EPHEMERAL)))
+    }
+}
+
+#[async_trait]
+impl AutocompleteTrait for ChecklistTodoCommand {
+    async fn autocomplete(discord: &Discord, command: &CommandInteraction) -> serenity::Result<CreateAutocompleteResponse> {
+        todo_choices(discord, command).await
+    }
+}
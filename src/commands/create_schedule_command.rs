@@ -1,13 +1,84 @@
-use serenity::all::{CommandInteraction, CreateInteractionResponseMessage};
-use serenity::async_trait;
-use crate::commands::CommandTrait;
-use crate::global::discord::Discord;
+use serenity::all::{CommandDataOptionValue, CommandInteraction, CreateEmbed, CreateInteractionResponseMessage};
+use serenity::{async_trait, Error};
+use crate::commands::{find_option, sub_options, CommandTrait};
+use crate::database::meta_data_repo::MetaDataRepo;
+use crate::database::schedule_repo::ScheduleRepo;
+use crate::entity::schedule::Schedule;
+use crate::global::discord::{Discord, Guild};
+use crate::util::colour::GREEN;
+use crate::util::deadline::{now, parse_deadline, INVALID_DEADLINE_MESSAGE};
 
 pub struct CreateScheduleCommand;
 
 #[async_trait]
 impl CommandTrait for CreateScheduleCommand {
     async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
-        Ok(None)
+        let options = sub_options(command, "add")?;
+        let content = match find_option(options, "content") {
+            Some(CommandDataOptionValue::String(content)) => content.trim(),
+            _ => Err(Error::Other("일정 내용을 입력해주세요"))?
+        };
+        if content.is_empty() {
+            Err(Error::Other("일정 내용을 입력해주세요"))?
+        }
+        let deadline = match find_option(options, "deadline") {
+            Some(CommandDataOptionValue::String(deadline)) => deadline,
+            _ => Err(Error::Other(INVALID_DEADLINE_MESSAGE))?
+        };
+        let timezone = MetaDataRepo::new(Guild::from(discord), discord.database.clone()).get_timezone().await?;
+        let deadline = parse_deadline(deadline, now(timezone)).ok_or_else(|| Error::Other(INVALID_DEADLINE_MESSAGE))?;
+
+        let schedule = Schedule { content: content.to_string(), deadline };
+        ScheduleRepo::new(Guild::from(discord), discord.database.clone()).create_schedule(schedule.clone()).await?;
+
+        let create_embed = CreateEmbed::new()
+            .title("일정 추가 성공")
+            .description(schedule.label())
+            .color(GREEN);
+        Ok(Some(CreateInteractionResponseMessage::new().add_embed(create_embed)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serenity::json::json;
+
+    use crate::commands::CommandTrait;
+    use crate::commands::create_schedule_command::CreateScheduleCommand;
+    use crate::commands::get_schedule_command::GetScheduleCommand;
+    use crate::database::database::Database;
+    use crate::global::fake_discord::{command_interaction, discord, FakeDiscord};
+
+    #[tokio::test]
+    async fn adds_and_lists_schedules() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/일정 add");
+        for (content, deadline) in [("데모데이", "2099-05-02"), ("해커톤", "2099-03-02")] {
+            let command = command_interaction("일정", "add", json!([
+                { "name": "content", "type": 3, "value": content },
+                { "name": "deadline", "type": 3, "value": deadline }
+            ]));
+            CreateScheduleCommand::run(&discord, &command).await.unwrap();
+        }
+        assert_eq!(api.stored_entity().schedules.len(), 2);
+
+        let message = GetScheduleCommand::run(&discord, &command_interaction("일정", "show", json!([]))).await.unwrap().unwrap();
+
+        let description = serenity::json::to_value(message).unwrap()["embeds"][0]["description"].as_str().unwrap().to_string();
+        assert!(description.find("해커톤").unwrap() < description.find("데모데이").unwrap());
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_deadline() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/일정 add");
+        let command = command_interaction("일정", "add", json!([
+            { "name": "content", "type": 3, "value": "데모데이" },
+            { "name": "deadline", "type": 3, "value": "언젠가" }
+        ]));
+
+        assert!(CreateScheduleCommand::run(&discord, &command).await.is_err());
     }
-}
\ No newline at end of file
+}
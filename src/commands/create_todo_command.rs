@@ -1,7 +1,8 @@
 use serenity::{async_trait, Result};
-use serenity::all::{ButtonStyle, CommandInteraction, CreateActionRow, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage, InteractionResponseFlags};
+use serenity::all::{ButtonStyle, CommandDataOptionValue, CommandInteraction, CreateActionRow, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage, InteractionResponseFlags};
 
-use crate::commands::CommandTrait;
+use crate::commands::{find_option, sub_options, CommandTrait};
+use crate::component::create_todo_component::THREAD_PREFIX;
 use crate::database::team_repo::TeamRepo;
 use crate::global::discord::{Discord, Guild};
 
@@ -10,9 +11,12 @@ pub struct AskTeamCommand;
 #[async_trait]
 impl CommandTrait for AskTeamCommand {
     async fn run(discord: &Discord, command: &CommandInteraction) -> Result<Option<CreateInteractionResponseMessage>> {
-        let teams = TeamRepo::new(Guild::from(discord)).get_teams().await?;
+        // `/todo show`도 같은 팀 선택을 쓰므로 옵션이 없을 수 있음
+        let thread_option = sub_options(command, "add").ok().and_then(|options| find_option(options, "thread"));
+        let with_thread = matches!(thread_option, Some(CommandDataOptionValue::Boolean(true)));
+        let teams = TeamRepo::new(Guild::from(discord), discord.database.clone()).get_teams().await?;
         let buttons = teams.iter()
-            .map(|team| CreateButton::new(&team.name)
+            .map(|team| CreateButton::new(if with_thread { format!("{THREAD_PREFIX}{}", team.name) } else { team.name.clone() })
                 .label(&team.name)
                 .style(ButtonStyle::Secondary)
             )
@@ -25,11 +29,51 @@ impl CommandTrait for AskTeamCommand {
             .components(vec![team_selector])
             .flags(InteractionResponseFlags::EPHEMERAL);
         let builder = CreateInteractionResponse::Message(message);
-        if let Err(why) = command.create_response(&discord.ctx.http, builder).await {
+        if let Err(why) = discord.api.create_response(&command.into(), builder).await {
             println!("응답할 수 없습니다: {why}");
             return Err(why)
         }
 
         Ok(None)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serenity::json::json;
+
+    use crate::commands::CommandTrait;
+    use crate::commands::create_todo_command::AskTeamCommand;
+    use crate::database::database::Database;
+    use crate::global::fake_discord::{command_interaction, discord, FakeDiscord};
+
+    #[tokio::test]
+    async fn responds_with_team_buttons() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/todo add");
+        let command = command_interaction("todo", "add", json!([]));
+
+        let result = AskTeamCommand::run(&discord, &command).await.unwrap();
+
+        assert!(result.is_none());
+        let responses = api.responses();
+        assert_eq!(responses.len(), 1);
+        let buttons = responses[0]["data"]["components"][0]["components"].as_array().unwrap();
+        let labels: Vec<&str> = buttons.iter().map(|button| button["label"].as_str().unwrap()).collect();
+        assert_eq!(labels, vec!["iOS", "Android", "Web", "Server"]);
+        assert_eq!(buttons[0]["custom_id"], "iOS");
+    }
+
+    #[tokio::test]
+    async fn marks_team_buttons_for_thread() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/todo add");
+        let command = command_interaction("todo", "add", json!([{ "name": "thread", "type": 5, "value": true }]));
+
+        AskTeamCommand::run(&discord, &command).await.unwrap();
+
+        let responses = api.responses();
+        assert_eq!(responses[0]["data"]["components"][0]["components"][0]["custom_id"], "thread:iOS");
+    }
+}
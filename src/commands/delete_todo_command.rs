@@ -0,0 +1,85 @@
+use serenity::all::{CommandDataOptionValue, CommandInteraction, CreateAutocompleteResponse, CreateEmbed, CreateInteractionResponseMessage, InteractionResponseFlags};
+use serenity::{async_trait, Error};
+use crate::commands::{find_option, sub_options, todo_choices, AutocompleteTrait, CommandTrait};
+use crate::database::todo_repo::TodoRepo;
+use crate::global::discord::{Discord, Guild};
+use crate::util::colour::GREEN;
+
+pub struct DeleteTodoCommand;
+
+#[async_trait]
+impl CommandTrait for DeleteTodoCommand {
+    async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        let options = sub_options(command, "delete")?;
+        let id = match find_option(options, "todo") {
+            Some(CommandDataOptionValue::Integer(id)) => *id as u64,
+            _ => Err(Error::Other("투두를 선택해주세요"))?
+        };
+
+        let todo_repo = TodoRepo::new(Guild::from(discord), discord.database.clone());
+        let todo = todo_repo.delete_todo(id).await?;
+
+        let create_embed = CreateEmbed::new()
+            .title("투두 삭제 성공")
+            .description(todo.label())
+            .color(GREEN);
+        Ok(Some(CreateInteractionResponseMessage::new()
+            .flags(InteractionResponseFlags::EPHEMERAL)
+            .add_embed(create_embed)))
+    }
+}
+
+#[async_trait]
+impl AutocompleteTrait for DeleteTodoCommand {
+    async fn autocomplete(discord: &Discord, command: &CommandInteraction) -> serenity::Result<CreateAutocompleteResponse> {
+        todo_choices(discord, command).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serenity::json::json;
+
+    use crate::commands::{AutocompleteTrait, CommandTrait};
+    use crate::commands::delete_todo_command::DeleteTodoCommand;
+    use crate::database::database::{Database, DatabaseTrait};
+    use crate::global::fake_discord::{command_interaction, create_test_todo, discord, test_content, FakeDiscord};
+
+    async fn create_todos(api: &Arc<FakeDiscord>, database: &Arc<dyn DatabaseTrait>, contents: &[&str]) {
+        for content in contents {
+            create_test_todo(api, database, "iOS", &test_content(content)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn deletes_only_selected_todo() {
+        let api = Arc::new(FakeDiscord::new());
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        create_todos(&api, &database, &["로그인", "회원가입", "설정"]).await;
+        let discord = discord(api.clone(), database, "/todo delete");
+        let command = command_interaction("todo", "delete", json!([{ "name": "todo", "type": 4, "value": 2 }]));
+
+        DeleteTodoCommand::run(&discord, &command).await.unwrap();
+
+        let entity = api.stored_entity();
+        let ids: Vec<u64> = entity.todos.iter().map(|todo| todo.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(entity.next_todo_id, 4);
+    }
+
+    #[tokio::test]
+    async fn autocompletes_matching_todos() {
+        let api = Arc::new(FakeDiscord::new());
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        create_todos(&api, &database, &["로그인", "회원가입"]).await;
+        let discord = discord(api.clone(), database, "/todo delete");
+        let command = command_interaction("todo", "delete", json!([{ "name": "todo", "type": 4, "value": "회원", "focused": true }]));
+
+        let response = DeleteTodoCommand::autocomplete(&discord, &command).await.unwrap();
+
+        let response = serenity::json::to_value(response).unwrap();
+        assert_eq!(response["choices"], json!([{ "name": "[iOS] 03/02 회원가입", "value": 2 }]));
+    }
+}
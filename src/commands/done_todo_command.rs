@@ -0,0 +1,78 @@
+use serenity::all::{CommandDataOptionValue, CommandInteraction, CreateAutocompleteResponse, CreateEmbed, CreateInteractionResponseMessage, InteractionResponseFlags};
+use serenity::{async_trait, Error};
+use crate::commands::{find_option, sub_options, todo_choices, AutocompleteTrait, CommandTrait};
+use crate::database::todo_repo::TodoRepo;
+use crate::entity::todo::TodoStatus;
+use crate::global::discord::{Discord, Guild};
+use crate::util::colour::GREEN;
+
+pub struct DoneTodoCommand;
+
+#[async_trait]
+impl CommandTrait for DoneTodoCommand {
+    async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        let options = sub_options(command, "done")?;
+        let id = match find_option(options, "todo") {
+            Some(CommandDataOptionValue::Integer(id)) => *id as u64,
+            _ => Err(Error::Other("투두를 선택해주세요"))?
+        };
+
+        let todo_repo = TodoRepo::new(Guild::from(discord), discord.database.clone());
+        let todo = todo_repo.edit_status(id, TodoStatus::Done).await?;
+
+        let create_embed = CreateEmbed::new()
+            .title("투두 완료! 🎉")
+            .description(todo.label())
+            .color(GREEN);
+        Ok(Some(CreateInteractionResponseMessage::new()
+            .flags(InteractionResponseFlags::EPHEMERAL)
+            .add_embed(create_embed)))
+    }
+}
+
+#[async_trait]
+impl AutocompleteTrait for DoneTodoCommand {
+    async fn autocomplete(discord: &Discord, command: &CommandInteraction) -> serenity::Result<CreateAutocompleteResponse> {
+        todo_choices(discord, command).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serenity::json::json;
+
+    use crate::commands::CommandTrait;
+    use crate::commands::done_todo_command::DoneTodoCommand;
+    use crate::database::database::{Database, DatabaseTrait};
+    use crate::entity::todo::TodoStatus;
+    use crate::global::fake_discord::{command_interaction, create_test_todo, discord, test_content, FakeDiscord};
+
+    #[tokio::test]
+    async fn marks_todo_done() {
+        let api = Arc::new(FakeDiscord::new());
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        create_test_todo(&api, &database, "iOS", &test_content("로그인")).await;
+        create_test_todo(&api, &database, "iOS", &test_content("회원가입")).await;
+        let discord = discord(api.clone(), database, "/todo done");
+        let command = command_interaction("todo", "done", json!([{ "name": "todo", "type": 4, "value": 2 }]));
+
+        let message = DoneTodoCommand::run(&discord, &command).await.unwrap().unwrap();
+
+        let statuses: Vec<TodoStatus> = api.stored_entity().todos.iter().map(|todo| todo.todo.status).collect();
+        assert_eq!(statuses, vec![TodoStatus::Todo, TodoStatus::Done]);
+        let message = serenity::json::to_value(message).unwrap();
+        assert_eq!(message["embeds"][0]["title"], "투두 완료! 🎉");
+        assert_eq!(message["embeds"][0]["description"], "[iOS] 03/02 회원가입");
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_todo() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/todo done");
+        let command = command_interaction("todo", "done", json!([{ "name": "todo", "type": 4, "value": 7 }]));
+
+        assert!(DoneTodoCommand::run(&discord, &command).await.is_err());
+    }
+}
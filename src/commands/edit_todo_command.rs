@@ -0,0 +1,110 @@
+use serenity::all::{CommandDataOptionValue, CommandInteraction, CreateAutocompleteResponse, CreateEmbed, CreateInteractionResponseMessage, InteractionResponseFlags};
+use serenity::{async_trait, Error};
+use crate::commands::{find_option, sub_options, todo_choices, AutocompleteTrait, CommandTrait};
+use crate::database::meta_data_repo::MetaDataRepo;
+use crate::database::todo_repo::{TodoEdit, TodoRepo};
+use crate::entity::todo::{Recurrence, TodoContent, TodoPriority};
+use crate::global::discord::{Discord, Guild};
+use crate::util::colour::GREEN;
+use crate::util::deadline::{now, parse_deadline, INVALID_DEADLINE_MESSAGE};
+
+pub struct EditTodoCommand;
+
+#[async_trait]
+impl CommandTrait for EditTodoCommand {
+    async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        let options = sub_options(command, "edit")?;
+        let id = match find_option(options, "todo") {
+            Some(CommandDataOptionValue::Integer(id)) => *id as u64,
+            _ => Err(Error::Other("투두를 선택해주세요"))?
+        };
+        let mut edit = TodoEdit::default();
+        if let Some(CommandDataOptionValue::String(content)) = find_option(options, "content") {
+            edit.content = Some(content.clone());
+        }
+        if let Some(CommandDataOptionValue::String(deadline)) = find_option(options, "deadline") {
+            let timezone = MetaDataRepo::new(Guild::from(discord), discord.database.clone()).get_timezone().await?;
+            let deadline = parse_deadline(deadline, now(timezone)).ok_or_else(|| Error::Other(INVALID_DEADLINE_MESSAGE))?;
+            edit.deadline = Some(deadline);
+        }
+        if let Some(CommandDataOptionValue::String(priority)) = find_option(options, "priority") {
+            edit.priority = Some(TodoPriority::parse(priority).ok_or_else(|| Error::Other("중요도를 찾을 수 없습니다"))?);
+        }
+        if let Some(CommandDataOptionValue::String(labels)) = find_option(options, "labels") {
+            edit.labels = Some(TodoContent::parse_labels(labels));
+        }
+        let weeks = match find_option(options, "weeks") {
+            Some(CommandDataOptionValue::Integer(weeks)) => u32::try_from(*weeks).map_err(|_| Error::Other("반복 주기가 올바르지 않습니다"))?,
+            _ => 1
+        };
+        if let Some(CommandDataOptionValue::String(repeat)) = find_option(options, "repeat") {
+            edit.recurrence = match repeat.as_str() {
+                "none" => Some(None),
+                "weekly" => Some(Some(Recurrence::Weekly { weeks })),
+                "monthly" => Some(Some(Recurrence::Monthly { day: None })),
+                _ => Err(Error::Other("반복 주기를 찾을 수 없습니다"))?
+            };
+        }
+        if edit.is_empty() {
+            Err(Error::Other("수정할 내용을 입력해주세요"))?
+        }
+
+        let todo_repo = TodoRepo::new(Guild::from(discord), discord.database.clone());
+        let todo = todo_repo.edit_todo(id, &edit).await?;
+
+        let create_embed = CreateEmbed::new()
+            .title("투두 수정 성공")
+            .description(format!("{}{}{}", todo.label(), todo.todo.badges(), recurrence_description(&todo.todo)))
+            .color(GREEN);
+        Ok(Some(CreateInteractionResponseMessage::new()
+            .flags(InteractionResponseFlags::EPHEMERAL)
+            .add_embed(create_embed)))
+    }
+}
+
+fn recurrence_description(content: &TodoContent) -> String {
+    content.recurrence.map(|recurrence| format!("\n{} 반복", recurrence.description())).unwrap_or_default()
+}
+
+#[async_trait]
+impl AutocompleteTrait for EditTodoCommand {
+    async fn autocomplete(discord: &Discord, command: &CommandInteraction) -> serenity::Result<CreateAutocompleteResponse> {
+        todo_choices(discord, command).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serenity::json::json;
+
+    use crate::commands::CommandTrait;
+    use crate::commands::edit_todo_command::EditTodoCommand;
+    use crate::database::database::{Database, DatabaseTrait};
+    use crate::entity::todo::TodoPriority;
+    use crate::global::fake_discord::{command_interaction, create_test_todo, discord, test_content, test_deadline, FakeDiscord};
+
+    #[tokio::test]
+    async fn edits_content_and_keeps_id() {
+        let api = Arc::new(FakeDiscord::new());
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        create_test_todo(&api, &database, "iOS", &test_content("로그인")).await;
+        let discord = discord(api.clone(), database, "/todo edit");
+        let command = command_interaction("todo", "edit", json!([
+            { "name": "todo", "type": 4, "value": 1 },
+            { "name": "content", "type": 3, "value": "소셜 로그인" },
+            { "name": "priority", "type": 3, "value": "urgent" },
+            { "name": "labels", "type": 3, "value": "Auth, bug, auth" }
+        ]));
+
+        EditTodoCommand::run(&discord, &command).await.unwrap();
+
+        let todo = &api.stored_entity().todos[0];
+        assert_eq!(todo.id, 1);
+        assert_eq!(todo.todo.content, "소셜 로그인");
+        assert_eq!(todo.todo.deadline, test_deadline());
+        assert_eq!(todo.todo.priority, TodoPriority::Urgent);
+        assert_eq!(todo.todo.labels, vec!["auth", "bug"]);
+    }
+}
@@ -0,0 +1,29 @@
+use serenity::all::{CommandInteraction, CreateAttachment, CreateEmbed, CreateInteractionResponseMessage, InteractionResponseFlags};
+use serenity::async_trait;
+use crate::commands::{find_option, require_admin, sub_options, CommandTrait};
+use crate::database::database_repo::DatabaseRepo;
+use crate::global::discord::{Discord, Guild};
+use crate::util::colour::GREEN;
+
+pub struct ExportDBCommand;
+
+#[async_trait]
+impl CommandTrait for ExportDBCommand {
+    async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        require_admin(command)?;
+        let options = sub_options(command, "export")?;
+        let pretty = find_option(options, "pretty").and_then(|value| value.as_bool());
+
+        let database_repo = DatabaseRepo::new(Guild::from(discord), discord.database.clone());
+        let json = database_repo.export(pretty).await?;
+        let attachment = CreateAttachment::bytes(json, format!("database-{}.json", discord.guild_id));
+
+        let create_embed = CreateEmbed::new()
+            .title("DB 내보내기 성공! 📦")
+            .color(GREEN);
+        Ok(Some(CreateInteractionResponseMessage::new()
+            .flags(InteractionResponseFlags::EPHEMERAL)
+            .add_embed(create_embed)
+            .add_file(attachment)))
+    }
+}
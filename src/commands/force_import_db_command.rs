@@ -1,45 +1,163 @@
-use serenity::all::{CommandDataOptionValue, CommandInteraction, CreateEmbed, CreateInteractionResponseMessage, InteractionResponseFlags};
+use std::time::Duration;
+use serenity::all::{ButtonStyle, CommandDataOptionValue, CommandInteraction, CreateActionRow, CreateButton, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage, EditInteractionResponse, InteractionResponseFlags};
 use serenity::{async_trait, Error};
-use crate::commands::{CommandTrait, WOW_DESCRIPTION};
+use crate::commands::{find_option, require_admin, sub_options, CommandTrait, WOW_DESCRIPTION};
 use crate::database::database_repo::DatabaseRepo;
+use crate::entity::diff::EntityDiff;
+use crate::entity::entity::Entity;
 use crate::global::discord::{Discord, Guild};
-use crate::util::colour::GREEN;
+use crate::global::discord_api::InteractionHandle;
+use crate::util::colour::{GREEN, RED};
+
+const CONFIRM: &str = "force-import-confirm";
+const CANCEL: &str = "force-import-cancel";
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
 
 pub struct ForceImportDBCommand;
 
 #[async_trait]
 impl CommandTrait for ForceImportDBCommand {
     async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
-        let option = command.data.options.first().ok_or_else(|| Error::Other("에러"))?;
-        println!("{:?}", option);
-
-        if option.name != "force-import" {
-            Err(Error::Other("에러"))?
-        }
-
-        let command = match &option.value {
-            CommandDataOptionValue::SubCommand(value) => value,
-            _ => Err(Error::Other("에러"))?
-        }.first().ok_or_else(|| Error::Other("에러"))?;
+        require_admin(command)?;
+        let options = sub_options(command, "force-import")?;
 
-        if command.name != "json" {
-            Err(Error::Other("에러"))?
-        }
+        // json 문자열 또는 첨부파일
+        let json = match (find_option(options, "json"), find_option(options, "file")) {
+            (Some(CommandDataOptionValue::String(json)), _) => json.clone(),
+            (_, Some(CommandDataOptionValue::Attachment(attachment_id))) => {
+                let attachment = command.data.resolved.attachments.get(attachment_id)
+                    .ok_or_else(|| Error::Other("첨부파일을 찾을 수 없습니다"))?;
+                let bytes = attachment.download().await?;
+                String::from_utf8(bytes).map_err(|_| Error::Other("첨부파일이 UTF-8 텍스트가 아닙니다"))?
+            }
+            _ => Err(Error::Other("json 또는 file 중 하나를 입력해주세요"))?
+        };
 
-        let json = match &command.value {
-            CommandDataOptionValue::String(json) => json,
-            _ => Err(Error::Other("에러"))?
+        // 저장하기 전에 schema 검증
+        let entity = match Entity::from_json(&json) {
+            Ok(v) => v,
+            Err(why) => {
+                let create_embed = CreateEmbed::new()
+                    .title("올바른 DB 형식이 아닙니다 😅")
+                    .description(why.to_string())
+                    .color(RED);
+                return Ok(Some(CreateInteractionResponseMessage::new()
+                    .flags(InteractionResponseFlags::EPHEMERAL)
+                    .add_embed(create_embed)))
+            }
         };
 
-        let database_repo = DatabaseRepo::new(Guild::from(discord));
-        database_repo.force_import(json).await?;
+        let database_repo = DatabaseRepo::new(Guild::from(discord), discord.database.clone());
+        let current = database_repo.get().await.unwrap_or_else(|_| Entity::empty());
+        let diff = EntityDiff::between(&current, &entity);
 
+        // 미리보기 후 확인 버튼
         let create_embed = CreateEmbed::new()
-            .title("DB 강제 불러오기 성공! 🙄")
-            .description(WOW_DESCRIPTION)
-            .color(GREEN);
-        Ok(Some(CreateInteractionResponseMessage::new()
+            .title("DB를 덮어쓸까요? 🤔")
+            .description(diff.to_string());
+        let buttons = CreateActionRow::Buttons(vec![
+            CreateButton::new(CONFIRM).label("덮어쓰기").style(ButtonStyle::Danger),
+            CreateButton::new(CANCEL).label("취소").style(ButtonStyle::Secondary),
+        ]);
+        let message = CreateInteractionResponseMessage::new()
             .flags(InteractionResponseFlags::EPHEMERAL)
-            .add_embed(create_embed)))
+            .add_embed(create_embed)
+            .components(vec![buttons]);
+        let interaction = InteractionHandle::from(command);
+        discord.api.create_response(&interaction, CreateInteractionResponse::Message(message)).await?;
+
+        let response = discord.api.get_response(&interaction).await?;
+        let component = discord.api.await_component(response.id, command.user.id, CONFIRM_TIMEOUT).await;
+
+        match component {
+            Some(component) => {
+                let create_embed = if component.custom_id == CONFIRM {
+                    database_repo.force_import(entity).await?;
+                    CreateEmbed::new()
+                        .title("DB 강제 불러오기 성공! 🙄")
+                        .description(WOW_DESCRIPTION)
+                        .color(GREEN)
+                } else {
+                    cancel_create_embed()
+                };
+                let message = CreateInteractionResponseMessage::new()
+                    .add_embed(create_embed)
+                    .components(vec![]);
+                discord.api.create_response(&component.interaction, CreateInteractionResponse::UpdateMessage(message)).await?;
+            }
+            None => {
+                let builder = EditInteractionResponse::new()
+                    .embed(cancel_create_embed())
+                    .components(vec![]);
+                discord.api.edit_response(&interaction, builder).await?;
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+
+fn cancel_create_embed() -> CreateEmbed {
+    CreateEmbed::new()
+        .title("DB 강제 불러오기를 취소했습니다")
+        .color(RED)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serenity::all::Permissions;
+    use serenity::json::json;
+
+    use crate::commands::CommandTrait;
+    use crate::commands::force_import_db_command::{ForceImportDBCommand, CANCEL, CONFIRM};
+    use crate::database::database::Database;
+    use crate::entity::entity::Entity;
+    use crate::entity::team::Team;
+    use crate::global::fake_discord::{command_interaction, discord, FakeDiscord};
+
+    fn import_command() -> serenity::all::CommandInteraction {
+        let mut entity = Entity::empty();
+        entity.teams = vec![Team { name: String::from("Design") }];
+        let json = serenity::json::to_string(&entity).unwrap();
+        command_interaction("db", "force-import", json!([{ "name": "json", "type": 3, "value": json }]))
+    }
+
+    #[tokio::test]
+    async fn imports_after_confirm() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/db force-import");
+        api.push_component(CONFIRM);
+
+        ForceImportDBCommand::run(&discord, &import_command()).await.unwrap();
+
+        let teams: Vec<String> = api.stored_entity().teams.into_iter().map(|team| team.name).collect();
+        assert_eq!(teams, vec!["Design"]);
+    }
+
+    #[tokio::test]
+    async fn keeps_database_after_cancel() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/db force-import");
+        api.push_component(CANCEL);
+
+        ForceImportDBCommand::run(&discord, &import_command()).await.unwrap();
+
+        assert_eq!(api.stored_entity().teams.len(), 4);
+        assert_eq!(api.responses().last().unwrap()["data"]["embeds"][0]["title"], "DB 강제 불러오기를 취소했습니다");
+    }
+
+    #[tokio::test]
+    async fn requires_admin() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/db force-import");
+        let mut command = import_command();
+        command.member.as_mut().unwrap().permissions = Some(Permissions::empty());
+
+        let why = ForceImportDBCommand::run(&discord, &command).await.unwrap_err();
+        assert_eq!(why.to_string(), "관리자만 사용할 수 있습니다");
+        assert!(api.responses().is_empty());
     }
-}
\ No newline at end of file
+}
@@ -1,13 +1,32 @@
-use serenity::all::{CommandInteraction, CreateInteractionResponseMessage};
+use serenity::all::{CommandInteraction, CreateEmbed, CreateInteractionResponseMessage, InteractionResponseFlags};
 use serenity::async_trait;
 use crate::commands::CommandTrait;
-use crate::global::discord::Discord;
+use crate::database::schedule_repo::ScheduleRepo;
+use crate::global::discord::{Discord, Guild};
+use crate::util::colour::GREEN;
 
 pub struct GetScheduleCommand;
 
 #[async_trait]
 impl CommandTrait for GetScheduleCommand {
-    async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
-        Ok(None)
+    async fn run(discord: &Discord, _command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        let mut schedules = ScheduleRepo::new(Guild::from(discord), discord.database.clone()).get_schedule().await?;
+        schedules.sort_by_key(|schedule| schedule.deadline);
+
+        let mut message = String::new();
+        for schedule in &schedules {
+            message.push_str(&format!("### - {}\n", schedule.label()));
+        }
+        if schedules.is_empty() {
+            message.push_str("### 등록된 일정이 없습니다");
+        }
+
+        let create_embed = CreateEmbed::new()
+            .title("일정")
+            .description(message)
+            .color(GREEN);
+        Ok(Some(CreateInteractionResponseMessage::new()
+            .flags(InteractionResponseFlags::EPHEMERAL)
+            .add_embed(create_embed)))
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,40 @@
+use serenity::all::{CommandInteraction, CreateEmbed, CreateInteractionResponseMessage, InteractionResponseFlags};
+use serenity::async_trait;
+use crate::commands::{find_option, require_admin, sub_options, CommandTrait};
+use crate::database::audit_repo::AuditRepo;
+use crate::global::discord::{Discord, Guild};
+use crate::util::colour::GREEN;
+
+const PAGE_SIZE: usize = 10;
+
+pub struct HistoryDBCommand;
+
+#[async_trait]
+impl CommandTrait for HistoryDBCommand {
+    async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        require_admin(command)?;
+        let options = sub_options(command, "history")?;
+        let page = find_option(options, "page").and_then(|value| value.as_i64()).unwrap_or(1).max(1) as usize;
+
+        let audit_repo = AuditRepo::new(Guild::from(discord), discord.database.clone());
+        let entries = audit_repo.get_entries().await?;
+        let total_page = entries.len().div_ceil(PAGE_SIZE).max(1);
+
+        let mut message = String::new();
+        for entry in entries.iter().skip((page - 1) * PAGE_SIZE).take(PAGE_SIZE) {
+            message.push_str(&entry.message());
+            message.push('\n');
+        }
+        if message.is_empty() {
+            message.push_str("변경 기록이 없습니다");
+        }
+
+        let create_embed = CreateEmbed::new()
+            .title(format!("변경 기록 ({}/{})", page, total_page))
+            .description(message)
+            .color(GREEN);
+        Ok(Some(CreateInteractionResponseMessage::new()
+            .flags(InteractionResponseFlags::EPHEMERAL)
+            .add_embed(create_embed)))
+    }
+}
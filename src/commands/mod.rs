@@ -1,6 +1,7 @@
-use serenity::all::{CommandInteraction, CreateInteractionResponseMessage};
-use serenity::async_trait;
-use crate::global::discord::Discord;
+use serenity::all::{AutocompleteChoice, CommandDataOption, CommandDataOptionValue, CommandInteraction, CreateAutocompleteResponse, CreateInteractionResponseMessage};
+use serenity::{async_trait, Error};
+use crate::database::todo_repo::TodoRepo;
+use crate::global::discord::{Discord, Guild};
 
 
 pub mod reset_todos_command;
@@ -13,10 +14,75 @@ pub mod reset_db_command;
 pub mod force_import_db_command;
 pub mod create_schedule_command;
 pub mod get_schedule_command;
+pub mod export_db_command;
+pub mod restore_db_command;
+pub mod history_db_command;
+pub mod setting_log_channel_command;
+pub mod undo_command;
+pub mod edit_todo_command;
+pub mod delete_todo_command;
+pub mod done_todo_command;
+pub mod my_todos_command;
+pub mod checklist_todo_command;
+pub mod setting_timezone_command;
+pub mod setting_team_channel_command;
 
 #[async_trait]
 pub trait CommandTrait {
     async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>>;
 }
 
-const WOW_DESCRIPTION: &str = "짜잔";
\ No newline at end of file
+#[async_trait]
+pub trait AutocompleteTrait {
+    async fn autocomplete(discord: &Discord, command: &CommandInteraction) -> serenity::Result<CreateAutocompleteResponse>;
+}
+
+const WOW_DESCRIPTION: &str = "짜잔";
+// 디스코드 자동완성 선택지 최대 개수와 이름 최대 길이
+const MAX_CHOICES: usize = 25;
+const MAX_CHOICE_NAME: usize = 100;
+
+// `/{command} {sub_command} ...`의 하위 옵션들
+fn sub_options<'a>(command: &'a CommandInteraction, sub_command: &str) -> serenity::Result<&'a [CommandDataOption]> {
+    let option = command.data.options.first().ok_or_else(|| Error::Other("에러"))?;
+    if option.name != sub_command {
+        Err(Error::Other("에러"))?
+    }
+    match &option.value {
+        CommandDataOptionValue::SubCommand(value) => Ok(value),
+        _ => Err(Error::Other("에러"))
+    }
+}
+
+fn find_option<'a>(options: &'a [CommandDataOption], name: &str) -> Option<&'a CommandDataOptionValue> {
+    options.iter()
+        .find(|option| option.name == name)
+        .map(|option| &option.value)
+}
+
+fn is_admin(command: &CommandInteraction) -> bool {
+    command.member.as_ref()
+        .and_then(|member| member.permissions)
+        .is_some_and(|permissions| permissions.administrator())
+}
+
+fn require_admin(command: &CommandInteraction) -> serenity::Result<()> {
+    if !is_admin(command) {
+        Err(Error::Other("관리자만 사용할 수 있습니다"))?
+    }
+    Ok(())
+}
+
+// 입력한 내용이 포함된 투두들, 선택하면 투두 id가 입력됨
+async fn todo_choices(discord: &Discord, command: &CommandInteraction) -> serenity::Result<CreateAutocompleteResponse> {
+    let input = command.data.autocomplete().map(|option| option.value).unwrap_or_default();
+    let todo_repo = TodoRepo::new(Guild::from(discord), discord.database.clone());
+    let choices = todo_repo.get_todos().await?
+        .iter()
+        .map(|todo| (todo.id, todo.label()))
+        .filter(|(_, label)| label.contains(input))
+        .take(MAX_CHOICES)
+        .map(|(id, label)| AutocompleteChoice::new(label.chars().take(MAX_CHOICE_NAME).collect::<String>(), id))
+        .collect();
+    Ok(CreateAutocompleteResponse::new().set_choices(choices))
+}
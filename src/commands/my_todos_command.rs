@@ -0,0 +1,86 @@
+use serenity::all::{CommandInteraction, CreateInteractionResponseMessage, InteractionResponseFlags};
+use serenity::{async_trait, Error};
+use crate::commands::CommandTrait;
+use crate::database::todo_repo::TodoRepo;
+use crate::global::discord::{Discord, Guild};
+use crate::util::page::{page_embed, paginate, EMBED_DESCRIPTION_LIMIT};
+
+pub struct MyTodosCommand;
+
+#[async_trait]
+impl CommandTrait for MyTodosCommand {
+    async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        Ok(Some(Self::page(discord, &command.user.id.to_string(), 0).await?))
+    }
+}
+
+impl MyTodosCommand {
+    // 페이지 버튼에서도 같은 목록을 다시 그림, state는 담당자 유저 id
+    pub async fn page(discord: &Discord, state: &str, page: usize) -> serenity::Result<CreateInteractionResponseMessage> {
+        let user_id = state.parse().map_err(|_| Error::Other("목록 정보가 올바르지 않습니다"))?;
+        let todo_repo = TodoRepo::new(Guild::from(discord), discord.database.clone());
+        let mut todos = todo_repo.get_open_todos_by_assignee(user_id).await?;
+        todos.sort_by_key(|todo| todo.todo.deadline);
+
+        let mut message = String::new();
+        for todo in &todos {
+            message.push_str(&format!("### - {} {}{}\n", todo.todo.status.emoji(), todo.label(), todo.todo.progress()));
+        }
+        if todos.is_empty() {
+            message.push_str("### 맡은 일이 없네요. 쉬세요! 🤩");
+        }
+
+        let pages = paginate(&message, EMBED_DESCRIPTION_LIMIT);
+        let (create_embed, buttons) = page_embed(&pages, page, state);
+        let message = CreateInteractionResponseMessage::new()
+            .flags(InteractionResponseFlags::EPHEMERAL)
+            .add_embed(create_embed.title("내 투두"));
+        Ok(match buttons {
+            Some(buttons) => message.components(vec![buttons]),
+            None => message
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serenity::json::json;
+
+    use crate::commands::CommandTrait;
+    use crate::commands::my_todos_command::MyTodosCommand;
+    use crate::component::ComponentTrait;
+    use crate::component::page_component::PageComponent;
+    use crate::database::database::{Database, DatabaseTrait};
+    use crate::entity::todo::TodoContent;
+    use crate::global::fake_discord::{command_interaction, component_interaction, create_test_todo, discord, test_content, FakeDiscord, USER_ID};
+
+    #[tokio::test]
+    async fn pages_long_listing() {
+        let api = Arc::new(FakeDiscord::new());
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        for index in 0..40 {
+            let content = TodoContent { assignees: vec![USER_ID.get()], ..test_content(&format!("{index:02} {}", "긴 투두 ".repeat(20))) };
+            create_test_todo(&api, &database, "iOS", &content).await;
+        }
+        create_test_todo(&api, &database, "iOS", &test_content("다른 사람 투두")).await;
+        let discord = discord(api.clone(), database, "/todo mine");
+
+        let message = MyTodosCommand::run(&discord, &command_interaction("todo", "mine", json!([]))).await.unwrap().unwrap();
+
+        let message = serenity::json::to_value(message).unwrap();
+        assert_eq!(message["flags"], 64);
+        assert_eq!(message["embeds"][0]["title"], "내 투두");
+        assert!(message["embeds"][0]["description"].as_str().unwrap().chars().count() <= 4096);
+        assert_eq!(message["components"][0]["components"][1]["custom_id"], format!("page:1:{USER_ID}"));
+
+        PageComponent::run(&discord, &component_interaction("todo mine", &format!("page:1:{USER_ID}"))).await.unwrap();
+
+        let response = api.responses().pop().unwrap();
+        let description = response["data"]["embeds"][0]["description"].as_str().unwrap();
+        assert!(description.contains("39 긴 투두"));
+        assert!(!description.contains("다른 사람 투두"));
+        assert_eq!(response["data"]["embeds"][0]["footer"]["text"], "2 / 2");
+    }
+}
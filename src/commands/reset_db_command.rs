@@ -1,6 +1,7 @@
 use serenity::all::{CommandInteraction, CreateEmbed, CreateInteractionResponseMessage, InteractionResponseFlags};
 use serenity::async_trait;
-use crate::commands::{CommandTrait, WOW_DESCRIPTION};
+use crate::commands::{require_admin, CommandTrait, WOW_DESCRIPTION};
+use crate::database::backup_repo::BackupRepo;
 use crate::database::database_repo::DatabaseRepo;
 use crate::global::discord::{Discord, Guild};
 use crate::util::colour::GREEN;
@@ -9,8 +10,13 @@ pub struct ResetDBCommand;
 
 #[async_trait]
 impl CommandTrait for ResetDBCommand {
-    async fn run(discord: &Discord, _command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
-        DatabaseRepo::new(Guild::from(discord)).reset().await?;
+    async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        require_admin(command)?;
+        // 초기화 전 상태를 백업, 이미 깨진 DB라면 건너뜀
+        if let Err(why) = BackupRepo::new(Guild::from(discord), discord.database.clone(), &discord.backup).snapshot().await {
+            println!("초기화 전 백업 실패 {}", why);
+        }
+        DatabaseRepo::new(Guild::from(discord), discord.database.clone()).reset().await?;
         let create_embed = CreateEmbed::new()
             .title("DB가 초기화 됐습니다.")
             .description(WOW_DESCRIPTION)
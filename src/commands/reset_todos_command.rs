@@ -10,7 +10,7 @@ pub struct ResetTodosCommand;
 #[async_trait]
 impl CommandTrait for ResetTodosCommand {
     async fn run(discord: &Discord, _command: &CommandInteraction) -> Result<Option<CreateInteractionResponseMessage>> {
-        let todo_repo = TodoRepo::new(Guild::from(discord));
+        let todo_repo = TodoRepo::new(Guild::from(discord), discord.database.clone());
         todo_repo.reset_todo().await?;
 
         let create_embed = CreateEmbed::new()
@@ -0,0 +1,103 @@
+use serenity::all::{AutocompleteChoice, CommandDataOptionValue, CommandInteraction, CreateAutocompleteResponse, CreateEmbed, CreateInteractionResponseMessage, InteractionResponseFlags};
+use serenity::{async_trait, Error};
+use crate::commands::{find_option, require_admin, sub_options, AutocompleteTrait, CommandTrait, MAX_CHOICES, WOW_DESCRIPTION};
+use crate::database::backup_repo::BackupRepo;
+use crate::database::database_repo::DatabaseRepo;
+use crate::global::discord::{Discord, Guild};
+use crate::util::colour::GREEN;
+
+pub struct RestoreDBCommand;
+
+#[async_trait]
+impl CommandTrait for RestoreDBCommand {
+    async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        require_admin(command)?;
+        let options = sub_options(command, "restore")?;
+        let name = match find_option(options, "snapshot") {
+            Some(CommandDataOptionValue::String(name)) => name,
+            _ => Err(Error::Other("에러"))?
+        };
+
+        let backup_repo = BackupRepo::new(Guild::from(discord), discord.database.clone(), &discord.backup);
+        let entity = backup_repo.get_snapshot(name).await?;
+
+        // 복원도 되돌릴 수 있도록 현재 상태를 먼저 백업
+        backup_repo.snapshot().await?;
+        DatabaseRepo::new(Guild::from(discord), discord.database.clone()).force_import(entity).await?;
+
+        let create_embed = CreateEmbed::new()
+            .title(format!("{} 스냅샷으로 복원했습니다", name))
+            .description(WOW_DESCRIPTION)
+            .color(GREEN);
+        Ok(Some(CreateInteractionResponseMessage::new()
+            .flags(InteractionResponseFlags::EPHEMERAL)
+            .add_embed(create_embed)))
+    }
+}
+
+#[async_trait]
+impl AutocompleteTrait for RestoreDBCommand {
+    async fn autocomplete(discord: &Discord, command: &CommandInteraction) -> serenity::Result<CreateAutocompleteResponse> {
+        let input = command.data.autocomplete().map(|option| option.value).unwrap_or_default();
+        let backup_repo = BackupRepo::new(Guild::from(discord), discord.database.clone(), &discord.backup);
+        let choices = backup_repo.get_snapshots().await?
+            .into_iter()
+            .filter(|name| name.contains(input))
+            .take(MAX_CHOICES)
+            .map(|name| AutocompleteChoice::new(name.clone(), name))
+            .collect();
+        Ok(CreateAutocompleteResponse::new().set_choices(choices))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serenity::all::{CommandInteraction, Permissions};
+    use serenity::json::json;
+
+    use crate::commands::CommandTrait;
+    use crate::commands::restore_db_command::RestoreDBCommand;
+    use crate::database::backup_repo::BackupRepo;
+    use crate::database::database::Database;
+    use crate::database::database_repo::DatabaseRepo;
+    use crate::entity::entity::Entity;
+    use crate::entity::team::Team;
+    use crate::global::discord::Guild;
+    use crate::global::fake_discord::{command_interaction, discord, FakeDiscord};
+
+    fn restore_command(snapshot: &str) -> CommandInteraction {
+        command_interaction("db", "restore", json!([{ "name": "snapshot", "type": 3, "value": snapshot }]))
+    }
+
+    #[tokio::test]
+    async fn restores_snapshot() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/db restore");
+        let backup_repo = BackupRepo::new(Guild::from(&discord), discord.database.clone(), &discord.backup);
+        let name = backup_repo.snapshot().await.unwrap();
+
+        let mut entity = Entity::empty();
+        entity.teams = vec![Team { name: String::from("Design") }];
+        DatabaseRepo::new(Guild::from(&discord), discord.database.clone()).force_import(entity).await.unwrap();
+
+        RestoreDBCommand::run(&discord, &restore_command(&name)).await.unwrap();
+
+        assert_eq!(api.stored_entity().teams.len(), 4);
+        // 복원 전 상태도 스냅샷으로 남음
+        assert_eq!(backup_repo.get_snapshots().await.unwrap().len(), 2);
+        _ = tokio::fs::remove_dir_all(&discord.backup.path).await;
+    }
+
+    #[tokio::test]
+    async fn requires_admin() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/db restore");
+        let mut command = restore_command("20250101-000000");
+        command.member.as_mut().unwrap().permissions = Some(Permissions::empty());
+
+        let why = RestoreDBCommand::run(&discord, &command).await.unwrap_err();
+        assert_eq!(why.to_string(), "관리자만 사용할 수 있습니다");
+    }
+}
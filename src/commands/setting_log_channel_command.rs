@@ -0,0 +1,34 @@
+use serenity::all::{CommandDataOptionValue, CommandInteraction, CreateEmbed, CreateInteractionResponseMessage, InteractionResponseFlags};
+use serenity::async_trait;
+use crate::commands::{find_option, sub_options, CommandTrait};
+use crate::database::meta_data_repo::MetaDataRepo;
+use crate::global::discord::{Discord, Guild};
+use crate::util::colour::GREEN;
+
+pub struct SettingLogChannelCommand;
+
+#[async_trait]
+impl CommandTrait for SettingLogChannelCommand {
+    async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        let options = sub_options(command, "log-channel")?;
+        let channel_id = match find_option(options, "channel") {
+            Some(CommandDataOptionValue::Channel(value)) => Some(value.get()),
+            _ => None
+        };
+
+        let meta_data_repo = MetaDataRepo::new(Guild::from(discord), discord.database.clone());
+        meta_data_repo.edit_log_channel(channel_id).await?;
+
+        let description = match channel_id {
+            Some(_) => "### 변경 기록 채널 등록 성공! 📝",
+            None => "### 변경 기록 채널을 해제했습니다"
+        };
+        let create_embed = CreateEmbed::new()
+            .description(description)
+            .color(GREEN);
+
+        Ok(Some(CreateInteractionResponseMessage::new()
+            .flags(InteractionResponseFlags::EPHEMERAL)
+            .add_embed(create_embed)))
+    }
+}
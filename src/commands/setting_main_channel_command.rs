@@ -28,7 +28,7 @@ impl CommandTrait for SettingMainChannelCommand {
             _ => Err(Error::Other("에러"))?
         };
 
-        let meta_data_repo = MetaDataRepo::new(Guild::from(discord));
+        let meta_data_repo = MetaDataRepo::new(Guild::from(discord), discord.database.clone());
         meta_data_repo.edit_main_channel(channel_id.get()).await?;
 
         let create_embed = CreateEmbed::new()
@@ -0,0 +1,38 @@
+use serenity::all::{CommandDataOptionValue, CommandInteraction, CreateEmbed, CreateInteractionResponseMessage, InteractionResponseFlags};
+use serenity::{async_trait, Error};
+use crate::commands::{find_option, sub_options, CommandTrait};
+use crate::database::meta_data_repo::MetaDataRepo;
+use crate::global::discord::{Discord, Guild};
+use crate::util::colour::GREEN;
+
+pub struct SettingTeamChannelCommand;
+
+#[async_trait]
+impl CommandTrait for SettingTeamChannelCommand {
+    async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        let options = sub_options(command, "team-channel")?;
+        let team_name = match find_option(options, "team") {
+            Some(CommandDataOptionValue::String(value)) => value.trim(),
+            _ => Err(Error::Other("팀을 입력해주세요"))?
+        };
+        let channel_id = match find_option(options, "channel") {
+            Some(CommandDataOptionValue::Channel(value)) => Some(value.get()),
+            _ => None
+        };
+
+        let meta_data_repo = MetaDataRepo::new(Guild::from(discord), discord.database.clone());
+        meta_data_repo.edit_team_channel(team_name, channel_id).await?;
+
+        let description = match channel_id {
+            Some(channel_id) => format!("### {team_name} 팀 채널 등록 성공! 🧵\n투두 스레드는 <#{channel_id}>에 만들어집니다"),
+            None => format!("### {team_name} 팀 채널을 해제했습니다\n투두 스레드는 메인 채널에 만들어집니다")
+        };
+        let create_embed = CreateEmbed::new()
+            .description(description)
+            .color(GREEN);
+
+        Ok(Some(CreateInteractionResponseMessage::new()
+            .flags(InteractionResponseFlags::EPHEMERAL)
+            .add_embed(create_embed)))
+    }
+}
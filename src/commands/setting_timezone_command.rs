@@ -0,0 +1,65 @@
+use chrono_tz::Tz;
+use serenity::all::{CommandDataOptionValue, CommandInteraction, CreateEmbed, CreateInteractionResponseMessage, InteractionResponseFlags};
+use serenity::{async_trait, Error};
+use crate::commands::{find_option, sub_options, CommandTrait};
+use crate::database::meta_data_repo::MetaDataRepo;
+use crate::global::discord::{Discord, Guild};
+use crate::util::colour::GREEN;
+use crate::util::deadline::now;
+
+pub struct SettingTimezoneCommand;
+
+#[async_trait]
+impl CommandTrait for SettingTimezoneCommand {
+    async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        let options = sub_options(command, "timezone")?;
+        let timezone: Tz = match find_option(options, "timezone") {
+            Some(CommandDataOptionValue::String(value)) => value.trim().parse()
+                .map_err(|_| Error::Other("시간대를 찾을 수 없습니다. ex. Asia/Seoul"))?,
+            _ => Err(Error::Other("시간대를 입력해주세요"))?
+        };
+
+        let meta_data_repo = MetaDataRepo::new(Guild::from(discord), discord.database.clone());
+        meta_data_repo.edit_timezone(timezone).await?;
+
+        let create_embed = CreateEmbed::new()
+            .description(format!("### 시간대 설정 성공! 🕒\n{} (지금 {})", timezone.name(), now(timezone).format("%Y-%m-%d %H:%M")))
+            .color(GREEN);
+
+        Ok(Some(CreateInteractionResponseMessage::new()
+            .flags(InteractionResponseFlags::EPHEMERAL)
+            .add_embed(create_embed)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serenity::json::json;
+
+    use crate::commands::CommandTrait;
+    use crate::commands::setting_timezone_command::SettingTimezoneCommand;
+    use crate::database::database::Database;
+    use crate::global::fake_discord::{command_interaction, discord, FakeDiscord};
+
+    #[tokio::test]
+    async fn stores_timezone() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/설정 timezone");
+        let command = command_interaction("설정", "timezone", json!([{ "name": "timezone", "type": 3, "value": "America/New_York" }]));
+
+        SettingTimezoneCommand::run(&discord, &command).await.unwrap();
+
+        assert_eq!(api.stored_entity().meta_data.timezone.as_deref(), Some("America/New_York"));
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_timezone() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/설정 timezone");
+        let command = command_interaction("설정", "timezone", json!([{ "name": "timezone", "type": 3, "value": "Mars/Olympus" }]));
+
+        assert!(SettingTimezoneCommand::run(&discord, &command).await.is_err());
+    }
+}
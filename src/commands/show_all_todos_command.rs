@@ -1,35 +1,170 @@
-use serenity::all::{CommandInteraction, CreateEmbed, CreateInteractionResponseMessage};
-use serenity::async_trait;
-use crate::commands::CommandTrait;
+use serenity::all::{CommandDataOptionValue, CommandInteraction, CreateInteractionResponseMessage};
+use serenity::{async_trait, Error};
+use crate::commands::{sub_options, CommandTrait};
+use crate::database::meta_data_repo::MetaDataRepo;
 use crate::database::team_repo::TeamRepo;
-use crate::database::todo_repo::TodoRepo;
-use crate::entity::todo::{Todo, VecTodoExtension};
+use crate::database::todo_repo::{TodoQuery, TodoRepo, TodoSort};
+use crate::entity::todo::{Todo, TodoPriority, VecTodoExtension};
 use crate::global::discord::{Discord, Guild};
-use crate::util::colour::GREEN;
+use crate::util::deadline::{now, parse_date};
+use crate::util::page::{paginate, page_message, EMBED_DESCRIPTION_LIMIT};
 
 pub struct ShowAllTodosCommand;
 
 #[async_trait]
 impl CommandTrait for ShowAllTodosCommand {
-    async fn run(discord: &Discord, _command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
-        let team_repo = TeamRepo::new(Guild::from(discord));
-        let todo_repo = TodoRepo::new(Guild::from(discord));
+    async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        let options = sub_options(command, "show-all")?;
+        let timezone = MetaDataRepo::new(Guild::from(discord), discord.database.clone()).get_timezone().await?;
+        let today = now(timezone).date_naive();
+
+        let mut query = TodoQuery::default();
+        for option in options {
+            match (option.name.as_str(), &option.value) {
+                ("team", CommandDataOptionValue::String(team)) => query.team = Some(team.trim().to_string()),
+                ("label", CommandDataOptionValue::String(label)) => query.label = Some(label.trim().to_lowercase()),
+                ("priority", CommandDataOptionValue::String(priority)) => {
+                    query.min_priority = Some(TodoPriority::parse(priority).ok_or_else(|| Error::Other("중요도를 찾을 수 없습니다"))?);
+                }
+                ("assignee", CommandDataOptionValue::User(user_id)) => query.assignee = Some(user_id.get()),
+                ("search", CommandDataOptionValue::String(search)) => query.search = Some(search.trim().to_lowercase()),
+                ("from", CommandDataOptionValue::String(from)) => {
+                    query.from = Some(parse_date(from, today).ok_or_else(|| Error::Other("시작 날짜를 제대로 입력해주세요. ex. 3/2, 이번주 월요일"))?);
+                }
+                ("to", CommandDataOptionValue::String(to)) => {
+                    query.to = Some(parse_date(to, today).ok_or_else(|| Error::Other("끝 날짜를 제대로 입력해주세요. ex. 3/31, 월말"))?);
+                }
+                ("overdue", CommandDataOptionValue::Boolean(overdue)) => query.overdue = *overdue,
+                ("this-week", CommandDataOptionValue::Boolean(this_week)) => query.this_week = *this_week,
+                ("sort", CommandDataOptionValue::String(sort)) => {
+                    query.sort = TodoSort::parse(sort).ok_or_else(|| Error::Other("정렬 기준을 찾을 수 없습니다"))?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Some(Self::page(discord, &query.state(), 0).await?))
+    }
+}
+
+impl ShowAllTodosCommand {
+    // 페이지 버튼에서도 같은 목록을 다시 그림, state는 `TodoQuery::state`
+    pub async fn page(discord: &Discord, state: &str, page: usize) -> serenity::Result<CreateInteractionResponseMessage> {
+        let query = TodoQuery::parse(state).ok_or_else(|| Error::Other("목록 정보가 올바르지 않습니다"))?;
+        let timezone = MetaDataRepo::new(Guild::from(discord), discord.database.clone()).get_timezone().await?;
+        let team_repo = TeamRepo::new(Guild::from(discord), discord.database.clone());
+        let todo_repo = TodoRepo::new(Guild::from(discord), discord.database.clone());
 
         let teams = team_repo.get_teams().await?;
-        let todos = todo_repo.get_todos().await?;
+        let todos = todo_repo.query(&query, now(timezone)).await?;
         let mut message = String::new();
-        for team in teams {
+        for team in teams.iter().filter(|team| query.team.as_ref().is_none_or(|name| team.name == *name)) {
             let todos: Vec<Todo> = todos.iter().filter(|todo| todo.team.name == team.name).cloned().collect();
-            let mut m = todos.message(&team.name);
+            let mut m = todos.message_in_order(&team.name);
             m.push_str("\n\n");
             message.push_str(m.as_str());
         }
-        println!("{}", message);
-        let create_embed = CreateEmbed::new()
-            .description(message)
-            .color(GREEN);
+        if message.is_empty() {
+            Err(Error::Other("팀을 찾을 수 없습니다"))?
+        }
+
+        let pages = paginate(&message, EMBED_DESCRIPTION_LIMIT);
+        Ok(page_message(&pages, page, &query.state()))
+    }
+}
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serenity::json::json;
+
+    use crate::commands::CommandTrait;
+    use crate::commands::show_all_todos_command::ShowAllTodosCommand;
+    use crate::database::database::{Database, DatabaseTrait};
+    use crate::entity::todo::{TodoContent, TodoPriority, TodoStatus};
+    use crate::component::ComponentTrait;
+    use crate::component::page_component::PageComponent;
+    use crate::global::fake_discord::{command_interaction, component_interaction, create_test_todo, discord, test_content, FakeDiscord};
+
+    #[tokio::test]
+    async fn filters_by_label_and_priority() {
+        let api = Arc::new(FakeDiscord::new());
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        for (content, priority, label) in [("로그인", TodoPriority::Urgent, "bug"), ("회원가입", TodoPriority::Low, "bug"), ("설정", TodoPriority::High, "design")] {
+            let content = TodoContent { priority, labels: vec![label.to_string()], ..test_content(content) };
+            create_test_todo(&api, &database, "iOS", &content).await;
+        }
+        let discord = discord(api.clone(), database, "/todo show-all");
+        let command = command_interaction("todo", "show-all", json!([
+            { "name": "label", "type": 3, "value": "BUG" },
+            { "name": "priority", "type": 3, "value": "high" }
+        ]));
+
+        let message = ShowAllTodosCommand::run(&discord, &command).await.unwrap().unwrap();
+
+        let description = serenity::json::to_value(message).unwrap()["embeds"][0]["description"].as_str().unwrap().to_string();
+        assert!(description.contains("로그인 🔥 `bug`"));
+        assert!(!description.contains("회원가입"));
+        assert!(!description.contains("설정"));
+    }
+
+    #[tokio::test]
+    async fn filters_by_team_and_search_sorted_by_priority() {
+        let api = Arc::new(FakeDiscord::new());
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        for (team, content, priority) in [("iOS", "로그인 화면", TodoPriority::Low), ("iOS", "로그인 API 연동", TodoPriority::Urgent), ("iOS", "설정", TodoPriority::High), ("Web", "로그인 화면", TodoPriority::High)] {
+            create_test_todo(&api, &database, team, &TodoContent { priority, ..test_content(content) }).await;
+        }
+        let discord = discord(api.clone(), database, "/todo show-all");
+        let command = command_interaction("todo", "show-all", json!([
+            { "name": "team", "type": 3, "value": "iOS" },
+            { "name": "search", "type": 3, "value": "로그인" },
+            { "name": "sort", "type": 3, "value": "priority" }
+        ]));
+
+        let message = ShowAllTodosCommand::run(&discord, &command).await.unwrap().unwrap();
+
+        let description = serenity::json::to_value(message).unwrap()["embeds"][0]["description"].as_str().unwrap().to_string();
+        assert!(!description.contains("## Web"));
+        assert!(!description.contains("설정"));
+        let urgent = description.find("로그인 API 연동").unwrap();
+        let low = description.find("로그인 화면").unwrap();
+        assert!(urgent < low);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_date() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/todo show-all");
+        let command = command_interaction("todo", "show-all", json!([{ "name": "from", "type": 3, "value": "언젠가" }]));
+
+        assert!(ShowAllTodosCommand::run(&discord, &command).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn pages_long_listing() {
+        let api = Arc::new(FakeDiscord::new());
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        for index in 0..40 {
+            let content = TodoContent { status: TodoStatus::Done, ..test_content(&format!("{index:02} {}", "긴 투두 ".repeat(20))) };
+            create_test_todo(&api, &database, "iOS", &content).await;
+        }
+        let discord = discord(api.clone(), database, "/todo show-all");
+        let command = command_interaction("todo", "show-all", json!([]));
+
+        let message = ShowAllTodosCommand::run(&discord, &command).await.unwrap().unwrap();
+
+        let message = serenity::json::to_value(message).unwrap();
+        assert!(message["embeds"][0]["description"].as_str().unwrap().chars().count() <= 4096);
+        assert!(message["embeds"][0]["description"].as_str().unwrap().contains("00 긴 투두"));
+        assert_eq!(message["components"][0]["components"][1]["custom_id"], "page:1:");
+
+        let component = component_interaction("todo show-all", "page:1:");
+        PageComponent::run(&discord, &component).await.unwrap();
 
-        Ok(Some(CreateInteractionResponseMessage::new()
-            .add_embed(create_embed)))
+        let response = api.responses().pop().unwrap();
+        assert_eq!(response["type"], 7);
+        assert!(response["data"]["embeds"][0]["description"].as_str().unwrap().contains("39 긴 투두"));
+        assert_eq!(response["data"]["embeds"][0]["footer"]["text"], "2 / 2");
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,25 @@
+use serenity::all::{CommandInteraction, CreateEmbed, CreateInteractionResponseMessage, InteractionResponseFlags};
+use serenity::async_trait;
+use crate::commands::{is_admin, CommandTrait};
+use crate::database::database_repo::DatabaseRepo;
+use crate::global::discord::{Discord, Guild};
+use crate::util::colour::GREEN;
+
+pub struct UndoCommand;
+
+#[async_trait]
+impl CommandTrait for UndoCommand {
+    async fn run(discord: &Discord, command: &CommandInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        let database_repo = DatabaseRepo::new(Guild::from(discord), discord.database.clone());
+        let entity = database_repo.undo(is_admin(command)).await?;
+        let summary = entity.audit_log.last().map(|entry| entry.summary.clone()).unwrap_or_default();
+
+        let create_embed = CreateEmbed::new()
+            .title("마지막 변경을 되돌렸습니다 ⏪")
+            .description(summary)
+            .color(GREEN);
+        Ok(Some(CreateInteractionResponseMessage::new()
+            .flags(InteractionResponseFlags::EPHEMERAL)
+            .add_embed(create_embed)))
+    }
+}
@@ -0,0 +1,84 @@
+use serenity::all::{ComponentInteraction, ComponentInteractionDataKind, CreateActionRow, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage, CreateSelectMenu, CreateSelectMenuKind};
+use serenity::{async_trait, Error};
+
+use crate::component::ComponentTrait;
+use crate::database::todo_repo::TodoRepo;
+use crate::entity::todo::{Todo, TodoId};
+use crate::global::discord::{Discord, Guild};
+use crate::util::colour::GREEN;
+
+// 유저 선택 메뉴 custom id `todo-assign:{todo id}`
+pub const TODO_ASSIGN_PREFIX: &str = "todo-assign:";
+const MAX_ASSIGNEES: u8 = 10;
+
+pub struct AssignTodoComponent;
+
+#[async_trait]
+impl ComponentTrait for AssignTodoComponent {
+    async fn run(discord: &Discord, component: &ComponentInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        let id = component.data.custom_id.strip_prefix(TODO_ASSIGN_PREFIX)
+            .and_then(|id| id.parse().ok())
+            .ok_or_else(|| Error::Other("투두를 찾을 수 없습니다"))?;
+        let assignees = match &component.data.kind {
+            ComponentInteractionDataKind::UserSelect { values } => values.iter().map(|user_id| user_id.get()).collect(),
+            _ => Err(Error::Other("담당자를 선택해주세요"))?
+        };
+
+        let todo_repo = TodoRepo::new(Guild::from(discord), discord.database.clone());
+        let todo = todo_repo.edit_assignees(id, assignees).await?;
+
+        let message = CreateInteractionResponseMessage::new()
+            .add_embed(assigned_create_embed(&todo))
+            .components(vec![assignee_select(todo.id)]);
+        discord.api.create_response(&component.into(), CreateInteractionResponse::UpdateMessage(message)).await?;
+
+        Ok(None)
+    }
+}
+
+// 투두를 추가한 뒤 담당자를 고르는 메뉴
+pub fn assignee_select(id: TodoId) -> CreateActionRow {
+    let menu = CreateSelectMenu::new(format!("{TODO_ASSIGN_PREFIX}{id}"), CreateSelectMenuKind::User { default_users: None })
+        .placeholder("담당자 선택")
+        .min_values(0)
+        .max_values(MAX_ASSIGNEES);
+    CreateActionRow::SelectMenu(menu)
+}
+
+fn assigned_create_embed(todo: &Todo) -> CreateEmbed {
+    let assignees = match todo.todo.assignees_mention() {
+        mention if mention.is_empty() => String::from("담당자 없음"),
+        mention => format!("담당자{mention}"),
+    };
+    CreateEmbed::new()
+        .title("담당자 지정 성공")
+        .description(format!("{}\n{}", todo.label(), assignees))
+        .color(GREEN)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serenity::all::UserId;
+
+    use crate::component::ComponentTrait;
+    use crate::component::assign_todo_component::AssignTodoComponent;
+    use crate::database::database::{Database, DatabaseTrait};
+    use crate::global::fake_discord::{create_test_todo, discord, test_content, user_select_interaction, FakeDiscord, USER_ID};
+
+    #[tokio::test]
+    async fn assigns_selected_users() {
+        let api = Arc::new(FakeDiscord::new());
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        create_test_todo(&api, &database, "iOS", &test_content("로그인")).await;
+        let discord = discord(api.clone(), database, "/todo add");
+        let component = user_select_interaction("todo add", "todo-assign:1", &[USER_ID, UserId::new(200)]);
+
+        AssignTodoComponent::run(&discord, &component).await.unwrap();
+
+        assert_eq!(api.stored_entity().todos[0].todo.assignees, vec![USER_ID.get(), 200]);
+        let description = api.responses().pop().unwrap()["data"]["embeds"][0]["description"].clone();
+        assert_eq!(description, "[iOS] 03/02 로그인\n담당자 <@100> <@200>");
+    }
+}
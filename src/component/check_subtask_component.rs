@@ -0,0 +1,102 @@
+use serenity::all::{ButtonStyle, ComponentInteraction, CreateActionRow, CreateButton, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::{async_trait, Error};
+
+use crate::component::ComponentTrait;
+use crate::database::todo_repo::TodoRepo;
+use crate::entity::todo::Todo;
+use crate::global::discord::{Discord, Guild};
+use crate::util::colour::GREEN;
+
+// 버튼 custom id `todo-check:{todo id}:{하위 작업 index}`
+pub const TODO_CHECK_PREFIX: &str = "todo-check:";
+const BUTTONS_PER_ROW: usize = 5;
+const MAX_BUTTONS: usize = 25;
+
+pub struct CheckSubtaskComponent;
+
+#[async_trait]
+impl ComponentTrait for CheckSubtaskComponent {
+    async fn run(discord: &Discord, component: &ComponentInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        let (id, index) = component.data.custom_id.strip_prefix(TODO_CHECK_PREFIX)
+            .and_then(|value| value.split_once(':'))
+            .and_then(|(id, index)| Some((id.parse().ok()?, index.parse().ok()?)))
+            .ok_or_else(|| Error::Other("하위 작업을 찾을 수 없습니다"))?;
+
+        let todo_repo = TodoRepo::new(Guild::from(discord), discord.database.clone());
+        let todo = todo_repo.toggle_subtask(id, index).await?;
+
+        let builder = CreateInteractionResponse::UpdateMessage(checklist_message(&todo));
+        discord.api.create_response(&component.into(), builder).await?;
+
+        Ok(None)
+    }
+}
+
+// 체크리스트와 하위 작업마다 체크 버튼
+pub fn checklist_message(todo: &Todo) -> CreateInteractionResponseMessage {
+    let mut description = String::new();
+    for (index, subtask) in todo.todo.checklist.iter().enumerate() {
+        let check = if subtask.done { "☑️" } else { "⬜" };
+        description.push_str(&format!("{check} {}. {}\n", index + 1, subtask.content));
+    }
+    if todo.todo.checklist.is_empty() {
+        description.push_str("하위 작업이 없습니다");
+    }
+    let create_embed = CreateEmbed::new()
+        .title(format!("{}{}", todo.label(), todo.todo.progress()))
+        .description(description)
+        .color(GREEN);
+
+    let buttons: Vec<CreateButton> = todo.todo.checklist.iter()
+        .enumerate()
+        .take(MAX_BUTTONS)
+        .map(|(index, subtask)| {
+            let style = if subtask.done { ButtonStyle::Success } else { ButtonStyle::Secondary };
+            CreateButton::new(format!("{TODO_CHECK_PREFIX}{}:{index}", todo.id))
+                .label(format!("{}", index + 1))
+                .style(style)
+        })
+        .collect();
+    let rows = buttons.chunks(BUTTONS_PER_ROW)
+        .map(|buttons| CreateActionRow::Buttons(buttons.to_vec()))
+        .collect();
+
+    CreateInteractionResponseMessage::new()
+        .add_embed(create_embed)
+        .components(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::component::ComponentTrait;
+    use crate::component::check_subtask_component::CheckSubtaskComponent;
+    use crate::database::database::{Database, DatabaseTrait};
+    use crate::database::todo_repo::TodoRepo;
+    use crate::entity::todo::VecTodoExtension;
+    use crate::global::discord::Guild;
+    use crate::global::fake_discord::{component_interaction, create_test_todo, discord, test_content, FakeDiscord, GUILD_ID};
+
+    #[tokio::test]
+    async fn toggles_subtask_and_shows_progress() {
+        let api = Arc::new(FakeDiscord::new());
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        create_test_todo(&api, &database, "iOS", &test_content("Auth 기능 구현")).await;
+        let todo_repo = TodoRepo::new(Guild::new(api.clone(), GUILD_ID), database.clone());
+        for subtask in ["로그인 화면", "토큰 저장", "자동 로그인"] {
+            todo_repo.add_subtask(1, subtask).await.unwrap();
+        }
+        let discord = discord(api.clone(), database, "/todo checklist");
+
+        CheckSubtaskComponent::run(&discord, &component_interaction("todo checklist", "todo-check:1:1")).await.unwrap();
+
+        let todos = api.stored_entity().todos;
+        let checked: Vec<bool> = todos[0].todo.checklist.iter().map(|subtask| subtask.done).collect();
+        assert_eq!(checked, vec![false, true, false]);
+        assert!(todos.message("iOS").contains("Auth 기능 구현 (1/3)"));
+        let response = api.responses().pop().unwrap();
+        assert_eq!(response["data"]["embeds"][0]["title"], "[iOS] 03/02 Auth 기능 구현 (1/3)");
+        assert_eq!(response["data"]["components"][0]["components"][1]["style"], 3);
+    }
+}
@@ -1,84 +1,209 @@
-use std::str::FromStr;
+use std::time::Duration;
 
-use chrono::NaiveDate;
 use serenity::{async_trait, Error};
-use serenity::all::{ComponentInteraction, CreateInputText, CreateInteractionResponse, CreateInteractionResponseMessage, CreateQuickModal, InputTextStyle, InteractionResponseFlags};
+use serenity::all::{ChannelId, ComponentInteraction, CreateInputText, CreateInteractionResponse, CreateInteractionResponseMessage, InputTextStyle, InteractionResponseFlags};
 use serenity::builder::CreateEmbed;
 
 use crate::component::ComponentTrait;
+use crate::component::assign_todo_component::assignee_select;
+use crate::database::meta_data_repo::MetaDataRepo;
 use crate::database::todo_repo::TodoRepo;
 use crate::entity::team::Team;
-use crate::entity::todo::{Todo, TodoContent};
+use crate::entity::todo::TodoContent;
 use crate::global::discord::{Discord, Guild};
 use crate::util::colour::GREEN;
 use crate::util::create_embed_extension::CreateEmbedExtension;
+use crate::util::deadline::{now, parse_deadline, INVALID_DEADLINE_MESSAGE};
+
+const MODAL_TIMEOUT: Duration = Duration::from_secs(600);
+// 팀 버튼 custom_id 앞에 붙으면 투두를 만든 뒤 스레드도 만듭니다
+pub const THREAD_PREFIX: &str = "thread:";
 
 pub struct CreateTodoComponent;
 
 #[async_trait]
 impl ComponentTrait for CreateTodoComponent {
     async fn run(discord: &Discord, component: &ComponentInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
-        let team_name = &component.data.custom_id;
-        let modal = CreateQuickModal::new("todo 추가")
-            .field(
-                CreateInputText::new(InputTextStyle::Short, "투두", "content")
-                    .placeholder("Auth 기능 구현")
-                    .min_length(1)
-                    .max_length(300)
-            )
-            .field(
-                CreateInputText::new(InputTextStyle::Short, "마감기한", "deadline")
-                    .placeholder("ex. 3월 2일 -> 3/2")
-                    .min_length(3)
-                    .max_length(5)
-            );
-        let response = component.quick_modal(&discord.ctx, modal).await?.ok_or_else(|| Error::Other("response is None"))?;
+        let custom_id = &component.data.custom_id;
+        let (team_name, with_thread) = match custom_id.strip_prefix(THREAD_PREFIX) {
+            Some(team_name) => (team_name, true),
+            None => (custom_id.as_str(), false)
+        };
+        let fields = vec![
+            CreateInputText::new(InputTextStyle::Short, "투두", "content")
+                .placeholder("Auth 기능 구현")
+                .min_length(1)
+                .max_length(300),
+            CreateInputText::new(InputTextStyle::Short, "마감기한", "deadline")
+                .placeholder("ex. 3/2, 내일 오후 3시, 다음주 금요일 18:00")
+                .min_length(2)
+                .max_length(30),
+        ];
+        let response = discord.api.quick_modal(&component.into(), "todo 추가", fields, MODAL_TIMEOUT).await?
+            .ok_or_else(|| Error::Other("response is None"))?;
         let inputs = &response.inputs;
         let (content, deadline) = (&inputs[0], &inputs[1]);
-        let d: Vec<&str> = deadline.split("/").collect();
-        println!("{:?}", d);
-        if d.iter().count() != 2 {
-            let create_embed = CreateEmbed::error_create_embed("마감일을 제대로 입력해주세요. \nex. 3월 2일 -> 3/2".to_string());
+        let timezone = MetaDataRepo::new(Guild::from(discord), discord.database.clone()).get_timezone().await?;
+        let Some(deadline_date) = parse_deadline(deadline, now(timezone)) else {
+            let create_embed = CreateEmbed::error_create_embed(INVALID_DEADLINE_MESSAGE.to_string());
             let message = CreateInteractionResponseMessage::new()
                 .add_embed(create_embed)
                 .flags(InteractionResponseFlags::EPHEMERAL);
             let builder = CreateInteractionResponse::Message(message);
-            if let Err(why) = response.interaction.create_response(&discord.ctx.http, builder).await {
+            if let Err(why) = discord.api.create_response(&response.interaction, builder).await {
                 println!("{}.0 Err - {}", file!(), why);
             };
             return Ok(None)
-        }
-
-        let m = u32::from_str(d[0]).map_err(|_| Error::Other("month 파싱 실패"))?;
-        let d = u32::from_str(d[1]).map_err(|_| Error::Other("day 파싱 실패"))?;
+        };
 
-        println!("{}, {}", m, d);
+        let todo_repo = TodoRepo::new(Guild::from(discord), discord.database.clone());
+        let team = Team { name: team_name.to_string() };
+        let todo_content = TodoContent::new(content.clone(), deadline_date);
+        let mut todo = todo_repo.create_todo(&team, &todo_content).await?;
 
-        let todo_repo = TodoRepo::new(Guild::from(discord));
-        let todo = Todo {
-            team: Team { name: team_name.clone() },
-            todo: TodoContent {
-                content: content.clone(),
-                deadline: NaiveDate::from_ymd_opt(2024, m, d).ok_or_else(|| Error::Other("NaiveDate 파싱 실패"))?,
-            },
-        };
-        todo_repo.create_todo(&todo).await?;
+        // 스레드를 만들지 못해도 투두는 이미 추가됐으므로 안내만 합니다
+        let mut thread = String::new();
+        if with_thread {
+            let meta_data_repo = MetaDataRepo::new(Guild::from(discord), discord.database.clone());
+            let result = match meta_data_repo.get_thread_channel(team_name).await {
+                Ok(channel_id) => todo_repo.create_thread(todo.id, ChannelId::new(channel_id)).await,
+                Err(why) => Err(why),
+            };
+            match result {
+                Ok(created) => {
+                    todo = created;
+                    thread = todo.todo.thread_id.map(|thread_id| format!("\n스레드 <#{thread_id}>")).unwrap_or_default();
+                }
+                Err(why) => thread = format!("\n스레드를 만들 수 없습니다 - {why}"),
+            }
+        }
 
         let create_embed = CreateEmbed::new()
             .title("투두추가 성공")
             .color(GREEN)
-            .description(format!("{}까지 {}", deadline, content));
+            .description(format!("{}까지 {}{thread}", deadline_date.timestamp(), content));
 
         let message = CreateInteractionResponseMessage::new()
             .flags(InteractionResponseFlags::EPHEMERAL)
-            .add_embed(create_embed.clone());
+            .add_embed(create_embed.clone())
+            .components(vec![assignee_select(todo.id)]);
 
         let builder = CreateInteractionResponse::Message(message);
 
-        if let Err(why) = response.interaction.create_response(&discord.ctx.http, builder).await {
+        if let Err(why) = discord.api.create_response(&response.interaction, builder).await {
             println!("{} Err.1 - {}", file!(), why);
         };
 
         Ok(None)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serenity::all::ChannelId;
+
+    use crate::component::ComponentTrait;
+    use crate::component::create_todo_component::CreateTodoComponent;
+    use crate::database::database::{Database, DatabaseTrait};
+    use crate::database::meta_data_repo::MetaDataRepo;
+    use crate::database::todo_repo::TodoRepo;
+    use crate::entity::todo::TodoStatus;
+    use crate::global::discord::Guild;
+    use crate::global::fake_discord::{component_interaction, discord, FakeDiscord, GUILD_ID, USER_ID};
+    use crate::util::deadline::INVALID_DEADLINE_MESSAGE;
+
+    #[tokio::test]
+    async fn stores_todo_from_modal() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/todo add");
+        let component = component_interaction("todo add", "iOS");
+        api.push_modal_inputs(&["Auth 기능 구현", "3/2"]);
+
+        CreateTodoComponent::run(&discord, &component).await.unwrap();
+
+        let entity = api.stored_entity();
+        assert_eq!(entity.todos.len(), 1);
+        assert_eq!(entity.todos[0].id, 1);
+        assert_eq!(entity.todos[0].team.name, "iOS");
+        assert_eq!(entity.todos[0].todo.content, "Auth 기능 구현");
+        assert_eq!(entity.todos[0].todo.deadline.label(), "03/02");
+        assert!(entity.todos[0].todo.deadline.all_day);
+        assert_eq!(entity.audit_log.last().unwrap().user_id, Some(USER_ID.get()));
+        let response = api.responses().pop().unwrap();
+        assert_eq!(response["data"]["embeds"][0]["title"], "투두추가 성공");
+        assert_eq!(response["data"]["components"][0]["components"][0]["custom_id"], "todo-assign:1");
+    }
+
+    #[tokio::test]
+    async fn stores_deadline_with_time() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/todo add");
+        let component = component_interaction("todo add", "iOS");
+        api.push_modal_inputs(&["배포", "3/2 오후 6시"]);
+
+        CreateTodoComponent::run(&discord, &component).await.unwrap();
+
+        let deadline = api.stored_entity().todos[0].todo.deadline;
+        assert_eq!(deadline.label(), "03/02 18:00");
+        assert_eq!(deadline.at.offset().local_minus_utc(), 9 * 3600);
+    }
+
+    #[tokio::test]
+    async fn creates_thread_in_team_channel() {
+        let api = Arc::new(FakeDiscord::new());
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        let team_channel = api.add_channel("ios");
+        let guild = Guild::new(api.clone(), GUILD_ID);
+        MetaDataRepo::new(guild.clone(), database.clone()).edit_team_channel("iOS", Some(team_channel.get())).await.unwrap();
+        let discord = discord(api.clone(), database.clone(), "/todo add");
+        let component = component_interaction("todo add", "thread:iOS");
+        api.push_modal_inputs(&["Auth 기능 구현", "3/2"]);
+
+        CreateTodoComponent::run(&discord, &component).await.unwrap();
+
+        let todo = api.stored_entity().todos[0].clone();
+        assert_eq!(todo.team.name, "iOS");
+        let thread_id = todo.todo.thread_id.unwrap();
+        assert_eq!(api.find_channel("[iOS] 03/02 Auth 기능 구현").map(|channel_id| channel_id.get()), Some(thread_id));
+        let description = api.responses().pop().unwrap()["data"]["embeds"][0]["description"].as_str().unwrap().to_string();
+        assert!(description.ends_with(&format!("스레드 <#{thread_id}>")));
+
+        let todo_repo = TodoRepo::new(guild, database);
+        todo_repo.edit_status(todo.id, TodoStatus::Done).await.unwrap();
+        assert!(api.is_archived(ChannelId::new(thread_id)));
+        todo_repo.edit_status(todo.id, TodoStatus::Todo).await.unwrap();
+        assert!(!api.is_archived(ChannelId::new(thread_id)));
+        todo_repo.delete_todo(todo.id).await.unwrap();
+        assert!(api.is_archived(ChannelId::new(thread_id)));
+    }
+
+    #[tokio::test]
+    async fn keeps_todo_without_thread_channel() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/todo add");
+        let component = component_interaction("todo add", "thread:iOS");
+        api.push_modal_inputs(&["Auth 기능 구현", "3/2"]);
+
+        CreateTodoComponent::run(&discord, &component).await.unwrap();
+
+        let entity = api.stored_entity();
+        assert_eq!(entity.todos.len(), 1);
+        assert_eq!(entity.todos[0].todo.thread_id, None);
+        let description = api.responses().pop().unwrap()["data"]["embeds"][0]["description"].as_str().unwrap().to_string();
+        assert!(description.contains("스레드를 만들 수 없습니다"));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_deadline() {
+        let api = Arc::new(FakeDiscord::new());
+        let discord = discord(api.clone(), Arc::new(Database::default()), "/todo add");
+        let component = component_interaction("todo add", "iOS");
+        api.push_modal_inputs(&["Auth 기능 구현", "32"]);
+
+        CreateTodoComponent::run(&discord, &component).await.unwrap();
+
+        assert!(api.stored_entity().todos.is_empty());
+        assert_eq!(api.responses().last().unwrap()["data"]["embeds"][0]["description"], INVALID_DEADLINE_MESSAGE);
+    }
+}
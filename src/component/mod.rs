@@ -6,6 +6,10 @@ use crate::global::discord::Discord;
 pub mod create_todo_component;
 pub mod not_found;
 pub mod show_todos_component;
+pub mod todo_status_component;
+pub mod assign_todo_component;
+pub mod check_subtask_component;
+pub mod page_component;
 
 #[async_trait]
 pub trait ComponentTrait {
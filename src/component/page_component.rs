@@ -0,0 +1,37 @@
+use serenity::all::{ComponentInteraction, CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::{async_trait, Error};
+
+use crate::commands::my_todos_command::MyTodosCommand;
+use crate::commands::show_all_todos_command::ShowAllTodosCommand;
+use crate::component::ComponentTrait;
+use crate::component::show_todos_component::{ShowTodosComponent, TEAM_STATE_PREFIX};
+use crate::global::discord::Discord;
+use crate::util::page::parse_page;
+
+pub struct PageComponent;
+
+#[async_trait]
+impl ComponentTrait for PageComponent {
+    async fn run(discord: &Discord, component: &ComponentInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        let (page, state) = parse_page(&component.data.custom_id)
+            .ok_or_else(|| Error::Other("페이지를 찾을 수 없습니다"))?;
+
+        // 페이지를 넘긴 목록을 만든 명령어
+        #[allow(deprecated)]
+        let interaction_name = component.message.interaction.as_ref()
+            .map(|interaction| interaction.name.as_str())
+            .unwrap_or_default();
+        let message = match interaction_name {
+            "todo show-all" => ShowAllTodosCommand::page(discord, state, page).await?,
+            "todo mine" => MyTodosCommand::page(discord, state, page).await?,
+            // 팀 목록은 버튼으로 연 메세지라 명령어 대신 state로 구분
+            _ if state.starts_with(TEAM_STATE_PREFIX) => ShowTodosComponent::page(discord, &state[TEAM_STATE_PREFIX.len()..], page).await?,
+            _ => Err(Error::Other("페이지를 넘길 수 없는 목록입니다"))?
+        };
+
+        let builder = CreateInteractionResponse::UpdateMessage(message);
+        discord.api.create_response(&component.into(), builder).await?;
+
+        Ok(None)
+    }
+}
@@ -1,11 +1,19 @@
-use serenity::all::{ComponentInteraction, CreateEmbed, CreateInteractionResponseMessage};
+use serenity::all::{ButtonStyle, ComponentInteraction, CreateActionRow, CreateButton, CreateInteractionResponseMessage};
 use serenity::async_trait;
 
 use crate::component::ComponentTrait;
+use crate::component::todo_status_component::TODO_STATUS_PREFIX;
 use crate::database::todo_repo::TodoRepo;
-use crate::entity::todo::VecTodoExtension;
+use crate::entity::todo::{Todo, VecTodoExtension};
 use crate::global::discord::{Discord, Guild};
-use crate::util::colour::GREEN;
+use crate::util::page::{page_embed, paginate, EMBED_DESCRIPTION_LIMIT};
+
+// 디스코드 메세지 하나에 달 수 있는 버튼은 5줄 x 5개, 여러 쪽이면 마지막 줄은 페이지 버튼
+const BUTTONS_PER_ROW: usize = 5;
+const MAX_ROWS: usize = 5;
+const MAX_LABEL: usize = 40;
+// 페이지 버튼 state `team={팀 이름}`
+pub const TEAM_STATE_PREFIX: &str = "team=";
 
 pub struct ShowTodosComponent;
 
@@ -14,15 +22,109 @@ impl ComponentTrait for ShowTodosComponent {
     async fn run(discord: &Discord, component: &ComponentInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
         let team_name = &component.data.custom_id;
 
-        let todo_repo = TodoRepo::new(Guild::from(discord));
-        let todos = todo_repo.get_todos_by_team(&team_name).await?;
+        let todo_repo = TodoRepo::new(Guild::from(discord), discord.database.clone());
+        let todos = todo_repo.get_todos_by_team(team_name).await?;
+
+        Ok(Some(todos_message(team_name, todos, 0)))
+    }
+}
+
+impl ShowTodosComponent {
+    // 페이지 버튼에서 같은 팀 목록을 다시 그림
+    pub async fn page(discord: &Discord, team_name: &str, page: usize) -> serenity::Result<CreateInteractionResponseMessage> {
+        let todo_repo = TodoRepo::new(Guild::from(discord), discord.database.clone());
+        let todos = todo_repo.get_todos_by_team(team_name).await?;
+        Ok(todos_message(team_name, todos, page))
+    }
+}
+
+// 팀 투두 목록의 page번째 쪽과 그 쪽에 보이는 투두의 상태를 바꾸는 버튼
+pub fn todos_message(team_name: &str, mut todos: Vec<Todo>, page: usize) -> CreateInteractionResponseMessage {
+    let pages = paginate(&todos.message(team_name), EMBED_DESCRIPTION_LIMIT);
+    let page = page.min(pages.len() - 1);
+    let (create_embed, page_buttons) = page_embed(&pages, page, &format!("{TEAM_STATE_PREFIX}{team_name}"));
+
+    // 한 줄에 투두 하나, 첫 쪽의 첫 줄은 팀 이름
+    todos.sort_by_key(|todo| todo.todo.deadline);
+    let start = pages[..page].iter().map(|page| page.lines().count()).sum::<usize>().saturating_sub(1);
+    let count = pages[page].lines().count() - usize::from(page == 0);
+    let max_buttons = BUTTONS_PER_ROW * (MAX_ROWS - usize::from(page_buttons.is_some()));
+    let buttons: Vec<CreateButton> = todos.iter()
+        .skip(start)
+        .take(count.min(max_buttons))
+        .map(|todo| {
+            let label: String = format!("{} {}", todo.todo.status.emoji(), todo.todo.content).chars().take(MAX_LABEL).collect();
+            CreateButton::new(format!("{TODO_STATUS_PREFIX}{}:{page}", todo.id))
+                .label(label)
+                .style(ButtonStyle::Secondary)
+        })
+        .collect();
+    let rows = buttons.chunks(BUTTONS_PER_ROW)
+        .map(|buttons| CreateActionRow::Buttons(buttons.to_vec()))
+        .chain(page_buttons)
+        .collect();
+
+    CreateInteractionResponseMessage::new()
+        .add_embed(create_embed)
+        .components(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serenity::json::Value;
+
+    use crate::component::ComponentTrait;
+    use crate::component::page_component::PageComponent;
+    use crate::component::show_todos_component::ShowTodosComponent;
+    use crate::component::todo_status_component::TodoStatusComponent;
+    use crate::database::database::{Database, DatabaseTrait};
+    use crate::global::fake_discord::{component_interaction, create_test_todo, discord, test_content, FakeDiscord};
+
+    fn custom_ids(message: &Value) -> Vec<String> {
+        message["components"].as_array().unwrap().iter()
+            .flat_map(|row| row["components"].as_array().unwrap().iter())
+            .map(|button| button["custom_id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn pages_long_team_listing() {
+        let api = Arc::new(FakeDiscord::new());
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        for index in 0..40 {
+            create_test_todo(&api, &database, "iOS", &test_content(&format!("{index:02} {}", "긴 투두 ".repeat(20)))).await;
+        }
+        let discord = discord(api.clone(), database, "/todo show");
+
+        let message = ShowTodosComponent::run(&discord, &component_interaction("todo show", "iOS")).await.unwrap().unwrap();
+
+        let message = serenity::json::to_value(message).unwrap();
+        let description = message["embeds"][0]["description"].as_str().unwrap();
+        assert!(description.chars().count() <= 4096);
+        assert!(description.starts_with("## iOS\n"));
+        assert_eq!(message["embeds"][0]["footer"]["text"], "1 / 2");
+        // 상태 버튼은 4줄까지, 마지막 줄은 페이지 버튼
+        let ids = custom_ids(&message);
+        assert_eq!(ids.len(), 22);
+        assert_eq!(ids[0], "todo-status:1:0");
+        assert_eq!(ids[21], "page:1:team=iOS");
+
+        PageComponent::run(&discord, &component_interaction("", "page:1:team=iOS")).await.unwrap();
 
-        let message = todos.message(team_name);
-        let create_embed = CreateEmbed::new()
-            .description(message)
-            .color(GREEN);
+        let response = api.responses().pop().unwrap();
+        let description = response["data"]["embeds"][0]["description"].as_str().unwrap();
+        assert!(description.contains("39 긴 투두"));
+        assert_eq!(response["data"]["embeds"][0]["footer"]["text"], "2 / 2");
+        // 두 번째 쪽의 버튼은 그 쪽에 보이는 투두
+        let first = custom_ids(&response["data"])[0].clone();
+        let id: u64 = first.trim_start_matches("todo-status:").trim_end_matches(":1").parse().unwrap();
+        assert!(description.starts_with(&format!("### - ⬜ <t:1709391599:D> {:02} ", id - 1)));
 
-        Ok(Some(CreateInteractionResponseMessage::new()
-            .add_embed(create_embed)))
+        // 두 번째 쪽에서 상태를 바꾸면 같은 쪽을 다시 그림
+        TodoStatusComponent::run(&discord, &component_interaction("", &first)).await.unwrap();
+        let response = api.responses().pop().unwrap();
+        assert_eq!(response["data"]["embeds"][0]["footer"]["text"], "2 / 2");
     }
-}
\ No newline at end of file
+}
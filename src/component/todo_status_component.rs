@@ -0,0 +1,81 @@
+use serenity::all::{ComponentInteraction, CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::{async_trait, Error};
+
+use crate::component::ComponentTrait;
+use crate::component::show_todos_component::todos_message;
+use crate::database::todo_repo::TodoRepo;
+use crate::global::discord::{Discord, Guild};
+
+// 버튼 custom id `todo-status:{todo id}:{목록 쪽}`, 쪽이 없는 이전 버튼은 첫 쪽
+pub const TODO_STATUS_PREFIX: &str = "todo-status:";
+
+pub struct TodoStatusComponent;
+
+#[async_trait]
+impl ComponentTrait for TodoStatusComponent {
+    async fn run(discord: &Discord, component: &ComponentInteraction) -> serenity::Result<Option<CreateInteractionResponseMessage>> {
+        let (id, page) = component.data.custom_id.strip_prefix(TODO_STATUS_PREFIX)
+            .and_then(parse_status_id)
+            .ok_or_else(|| Error::Other("투두를 찾을 수 없습니다"))?;
+
+        let todo_repo = TodoRepo::new(Guild::from(discord), discord.database.clone());
+        let todo = todo_repo.cycle_status(id).await?;
+
+        // 누른 메세지의 목록을 새 상태로 다시 그림
+        let todos = todo_repo.get_todos_by_team(&todo.team.name).await?;
+        let builder = CreateInteractionResponse::UpdateMessage(todos_message(&todo.team.name, todos, page));
+        discord.api.create_response(&component.into(), builder).await?;
+
+        Ok(None)
+    }
+}
+
+fn parse_status_id(value: &str) -> Option<(u64, usize)> {
+    match value.split_once(':') {
+        Some((id, page)) => Some((id.parse().ok()?, page.parse().ok()?)),
+        None => Some((value.parse().ok()?, 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::component::ComponentTrait;
+    use crate::component::todo_status_component::TodoStatusComponent;
+    use crate::database::database::{Database, DatabaseTrait};
+    use crate::entity::todo::TodoStatus;
+    use crate::global::fake_discord::{component_interaction, create_test_todo, discord, test_content, FakeDiscord};
+
+    #[tokio::test]
+    async fn cycles_status_and_updates_message() {
+        let api = Arc::new(FakeDiscord::new());
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        create_test_todo(&api, &database, "iOS", &test_content("로그인")).await;
+        let discord = discord(api.clone(), database, "/todo show");
+
+        for status in [TodoStatus::InProgress, TodoStatus::Done, TodoStatus::Todo] {
+            TodoStatusComponent::run(&discord, &component_interaction("todo show", "todo-status:1")).await.unwrap();
+            assert_eq!(api.stored_entity().todos[0].todo.status, status);
+        }
+
+        let response = api.responses().pop().unwrap();
+        assert_eq!(response["type"], 7);
+        assert_eq!(response["data"]["components"][0]["components"][0]["custom_id"], "todo-status:1:0");
+    }
+
+    #[tokio::test]
+    async fn cycles_from_stored_status_on_concurrent_clicks() {
+        let api = Arc::new(FakeDiscord::new());
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        create_test_todo(&api, &database, "iOS", &test_content("로그인")).await;
+        let discord = discord(api.clone(), database, "/todo show");
+        let component = component_interaction("todo show", "todo-status:1");
+
+        // 두 번 눌렀으면 같은 상태를 읽고 덮어쓰지 않고 두 단계 넘어감
+        let (first, second) = tokio::join!(TodoStatusComponent::run(&discord, &component), TodoStatusComponent::run(&discord, &component));
+        first.unwrap();
+        second.unwrap();
+        assert_eq!(api.stored_entity().todos[0].todo.status, TodoStatus::Done);
+    }
+}
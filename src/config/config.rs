@@ -1,16 +1,107 @@
 use std::env;
+use std::fmt;
 use dotenv::dotenv;
 
 pub struct Config {
     pub discord_bot_token: String,
-    pub is_json_pretty: bool
+    pub database_backend: DatabaseBackend,
+    pub database_path: String,
+    pub database_codec: Codec,
+    // 첫 번째가 현재 키, 나머지는 키를 바꾸기 전 데이터를 읽기 위한 이전 키
+    pub database_secrets: Vec<String>,
+    pub backup: BackupConfig,
+}
+
+// 스냅샷을 저장할 위치와 guild 별로 남길 개수
+// 스냅샷도 데이터베이스 채널과 같은 codec과 키로 저장합니다
+#[derive(Clone)]
+pub struct BackupConfig {
+    pub path: String,
+    pub retention: usize,
+    pub codec: Codec,
+    pub secrets: Vec<String>,
+}
+
+// 환경 변수가 잘못된 경우
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConfigError(pub String);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// 디스코드 채널에 저장할 때의 형식
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum Codec {
+    #[default]
+    Json,
+    // deflate 압축 후 base64
+    Deflate,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DatabaseBackend {
+    Discord,
+    File,
 }
 
 impl Config {
-    pub fn new() -> Self {
+    pub fn new() -> Result<Self, ConfigError> {
         dotenv().ok();
-        let discord_bot_token = env::var("DISCORD_BOT_TOKEN").expect("'DISCORD_BOT_TOKEN'를 .env에 추가해주세요");
-        let is_json_pretty: bool = env::var("IS_JSON_PRETTY").unwrap_or("false".to_string()).parse().unwrap();
-        Config { discord_bot_token, is_json_pretty }
+        let discord_bot_token = env::var("DISCORD_BOT_TOKEN")
+            .map_err(|_| ConfigError(String::from("'DISCORD_BOT_TOKEN'를 .env에 추가해주세요")))?;
+        let database_backend = match env::var("DATABASE_BACKEND").unwrap_or("discord".to_string()).as_str() {
+            "discord" => DatabaseBackend::Discord,
+            "file" => DatabaseBackend::File,
+            _ => Err(ConfigError(String::from("'DATABASE_BACKEND'는 discord 또는 file 이어야 합니다")))?
+        };
+        let database_path = env::var("DATABASE_PATH").unwrap_or("data".to_string());
+        let database_codec = match env::var("DATABASE_CODEC").unwrap_or("json".to_string()).as_str() {
+            "json" => Codec::Json,
+            "deflate" => Codec::Deflate,
+            _ => Err(ConfigError(String::from("'DATABASE_CODEC'는 json 또는 deflate 이어야 합니다")))?
+        };
+        let old_secrets = env::var("DATABASE_OLD_SECRETS").unwrap_or_default();
+        let database_secrets: Vec<String> = env::var("DATABASE_SECRET").ok().into_iter()
+            .chain(old_secrets.split(',').map(|secret| secret.trim().to_string()))
+            .filter(|secret| !secret.is_empty())
+            .collect();
+        let backup = BackupConfig {
+            path: env::var("BACKUP_PATH").unwrap_or("backups".to_string()),
+            retention: parse_retention(&env::var("BACKUP_RETENTION").unwrap_or("14".to_string()))?,
+            codec: database_codec,
+            secrets: database_secrets.clone(),
+        };
+        Ok(Config { discord_bot_token, database_backend, database_path, database_codec, database_secrets, backup })
     }
-}
\ No newline at end of file
+
+    // 토큰 없이 읽을 수 있어야 하는 값, 테스트에서도 사용됩니다
+    pub fn is_json_pretty() -> bool {
+        dotenv().ok();
+        env::var("IS_JSON_PRETTY").unwrap_or("false".to_string()).parse().unwrap()
+    }
+}
+
+// 스냅샷을 하나도 남기지 않으면 복원할 수 없으므로 1 이상이어야 합니다
+fn parse_retention(value: &str) -> Result<usize, ConfigError> {
+    match value.trim().parse() {
+        Ok(retention) if retention >= 1 => Ok(retention),
+        _ => Err(ConfigError(format!("'BACKUP_RETENTION'은 1 이상의 정수여야 합니다: {value}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::config::parse_retention;
+
+    #[test]
+    fn parses_retention() {
+        assert_eq!(parse_retention("14"), Ok(14));
+        assert_eq!(parse_retention(" 1 "), Ok(1));
+        assert!(parse_retention("0").is_err());
+        assert!(parse_retention("-1").is_err());
+        assert!(parse_retention("열넷").is_err());
+    }
+}
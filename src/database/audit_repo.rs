@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use serenity::all::{ChannelId, CreateMessage};
+use serenity::Result;
+
+use crate::database::database::DatabaseTrait;
+use crate::entity::audit::AuditEntry;
+use crate::entity::diff::EntityDiff;
+use crate::entity::entity::Entity;
+use crate::global::discord::Guild;
+
+// Entity와 함께 저장되므로 기록이 길어질수록 매번 수정할 메세지가 늘어남
+const MAX_AUDIT_LOG: usize = 20;
+
+pub struct AuditRepo {
+    guild: Guild,
+    database: Arc<dyn DatabaseTrait>,
+}
+
+impl AuditRepo {
+    pub fn new(guild: Guild, database: Arc<dyn DatabaseTrait>) -> Self {
+        AuditRepo { guild, database }
+    }
+
+    // 최신순
+    pub async fn get_entries(&self) -> Result<Vec<AuditEntry>> {
+        let entity = self.database.get_entity(&self.guild).await?;
+        Ok(entity.audit_log.into_iter().rev().collect())
+    }
+}
+
+// before -> entity 변경을 entity.audit_log에 기록
+pub fn record(guild: &Guild, before: &Entity, entity: &mut Entity) {
+    let (user_id, command) = match &guild.actor {
+        Some(actor) => (Some(actor.user_id.get()), actor.command.clone()),
+        None => (None, "schedule".to_string())
+    };
+    let entry = AuditEntry {
+        user_id,
+        command,
+        timestamp: Utc::now(),
+        summary: EntityDiff::between(before, entity).summary(),
+    };
+    entity.audit_log.push(entry);
+    if entity.audit_log.len() > MAX_AUDIT_LOG {
+        let overflow = entity.audit_log.len() - MAX_AUDIT_LOG;
+        entity.audit_log.drain(..overflow);
+    }
+}
+
+// 로그 채널이 설정돼 있으면 마지막 기록을 보냄
+pub async fn mirror(guild: &Guild, entity: &Entity) {
+    let (Some(channel_id), Some(entry)) = (entity.meta_data.log_channel_id, entity.audit_log.last()) else {
+        return;
+    };
+    let builder = CreateMessage::new().content(entry.message());
+    if let Err(why) = guild.api.send_message(ChannelId::new(channel_id), builder).await {
+        println!("변경 기록 전송 실패 {}", why);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::database::audit_repo::{mirror, record, MAX_AUDIT_LOG};
+    use crate::entity::entity::Entity;
+    use crate::entity::team::Team;
+    use crate::global::discord::{Actor, Guild};
+    use crate::global::fake_discord::{FakeDiscord, GUILD_ID, USER_ID};
+
+    fn guild(api: &Arc<FakeDiscord>) -> Guild {
+        let mut guild = Guild::new(api.clone(), GUILD_ID);
+        guild.actor = Some(Actor { user_id: USER_ID, command: String::from("/team add") });
+        guild
+    }
+
+    #[test]
+    fn keeps_latest_entries() {
+        let mut guild = guild(&Arc::new(FakeDiscord::new()));
+        let mut entity = Entity::empty();
+        for index in 0..MAX_AUDIT_LOG + 5 {
+            guild.actor.as_mut().unwrap().command = format!("/team add {index}");
+            let before = entity.clone();
+            entity.teams.push(Team { name: format!("team-{index}") });
+            record(&guild, &before, &mut entity);
+        }
+
+        // 오래된 기록부터 버림
+        let commands: Vec<&str> = entity.audit_log.iter().map(|entry| entry.command.as_str()).collect();
+        assert_eq!(commands.len(), MAX_AUDIT_LOG);
+        assert_eq!(commands[0], "/team add 5");
+        assert_eq!(*commands.last().unwrap(), format!("/team add {}", MAX_AUDIT_LOG + 4));
+        assert!(entity.audit_log.iter().all(|entry| entry.user_id == Some(USER_ID.get()) && entry.summary == "팀 +1"));
+    }
+
+    #[test]
+    fn records_schedule_without_actor() {
+        let guild = Guild::new(Arc::new(FakeDiscord::new()), GUILD_ID);
+        let before = Entity::empty();
+        let mut entity = before.clone();
+        record(&guild, &before, &mut entity);
+
+        let entry = entity.audit_log.last().unwrap();
+        assert_eq!((entry.user_id, entry.command.as_str()), (None, "schedule"));
+    }
+
+    #[tokio::test]
+    async fn mirrors_to_log_channel() {
+        let api = Arc::new(FakeDiscord::new());
+        let guild = guild(&api);
+        let channel_id = api.add_channel("mowgli-log");
+        let before = Entity::empty();
+        let mut entity = before.clone();
+        entity.teams.push(Team { name: String::from("QA") });
+        record(&guild, &before, &mut entity);
+
+        // 로그 채널이 없으면 보내지 않음
+        mirror(&guild, &entity).await;
+        assert!(api.message_contents(channel_id).is_empty());
+
+        entity.meta_data.log_channel_id = Some(channel_id.get());
+        mirror(&guild, &entity).await;
+        let contents = api.message_contents(channel_id);
+        assert_eq!(contents, vec![entity.audit_log.last().unwrap().message()]);
+        assert!(contents[0].ends_with(&format!("<@{}> `/team add` 팀 +1", USER_ID)));
+    }
+}
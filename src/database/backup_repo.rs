@@ -0,0 +1,163 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use chrono::Utc;
+use serenity::{Error, Result};
+
+use crate::config::config::{BackupConfig, Codec};
+use crate::database::database::{decode_entity, encode_entity, DatabaseTrait};
+use crate::entity::entity::Entity;
+use crate::global::discord::Guild;
+
+// `{BACKUP_PATH}/{guild_id}/{yyyymmdd-hhmmss-ffffff}.json` 형태로 스냅샷을 저장합니다
+// 내용은 데이터베이스 채널과 같은 codec과 키로 인코딩, 암호화됩니다
+pub struct BackupRepo {
+    guild: Guild,
+    database: Arc<dyn DatabaseTrait>,
+    path: PathBuf,
+    retention: usize,
+    codec: Codec,
+    secrets: Vec<String>,
+}
+
+impl BackupRepo {
+    pub fn new(guild: Guild, database: Arc<dyn DatabaseTrait>, config: &BackupConfig) -> Self {
+        let path = PathBuf::from(&config.path).join(guild.guild_id.to_string());
+        BackupRepo { guild, database, path, retention: config.retention, codec: config.codec, secrets: config.secrets.clone() }
+    }
+
+    // 현재 Entity를 저장하고 보관 개수를 넘은 오래된 스냅샷은 삭제
+    pub async fn snapshot(&self) -> Result<String> {
+        let entity = self.database.get_entity(&self.guild).await?;
+        let now = Utc::now().with_timezone(&chrono_tz::Asia::Seoul).format("%Y%m%d-%H%M%S-%6f").to_string();
+        tokio::fs::create_dir_all(&self.path).await?;
+
+        // 복원 전 백업처럼 같은 시각에 찍힌 스냅샷을 덮어쓰지 않도록 번호를 붙임
+        let mut name = now.clone();
+        let mut suffix = 1;
+        while tokio::fs::try_exists(self.snapshot_path(&name)).await? {
+            name = format!("{now}-{suffix}");
+            suffix += 1;
+        }
+        tokio::fs::write(self.snapshot_path(&name), encode_entity(&entity, self.codec, &self.secrets)?).await?;
+
+        for old in self.get_snapshots().await?.iter().skip(self.retention) {
+            tokio::fs::remove_file(self.snapshot_path(old)).await?;
+        }
+        Ok(name)
+    }
+
+    // 최신순
+    pub async fn get_snapshots(&self) -> Result<Vec<String>> {
+        let mut entries = match tokio::fs::read_dir(&self.path).await {
+            Ok(v) => v,
+            Err(why) if why.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(why) => Err(why)?
+        };
+        let mut names = vec![];
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|extension| extension == "json") {
+                if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        names.sort_by(|a, b| b.cmp(a));
+        Ok(names)
+    }
+
+    pub async fn get_snapshot(&self, name: &str) -> Result<Entity> {
+        // 경로 조작 방지
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_digit() || c == '-') {
+            Err(Error::Other("스냅샷 이름이 올바르지 않습니다"))?
+        }
+        let payload = match tokio::fs::read_to_string(self.snapshot_path(name)).await {
+            Ok(v) => v,
+            Err(why) if why.kind() == ErrorKind::NotFound => Err(Error::Other("스냅샷을 찾을 수 없습니다"))?,
+            Err(why) => Err(why)?
+        };
+        // 인코딩하기 전에 저장된 json 스냅샷도 그대로 읽음
+        decode_entity(&payload, &self.secrets)
+    }
+
+    fn snapshot_path(&self, name: &str) -> PathBuf {
+        self.path.join(format!("{name}.json"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::config::config::{BackupConfig, Codec};
+    use crate::database::backup_repo::BackupRepo;
+    use crate::database::database::Database;
+    use crate::entity::entity::Entity;
+    use crate::global::discord::Guild;
+    use crate::global::fake_discord::{backup_config, FakeDiscord, GUILD_ID};
+    use crate::util::crypto::is_encrypted;
+
+    fn backup_repo() -> BackupRepo {
+        backup_repo_with(backup_config())
+    }
+
+    fn backup_repo_with(config: BackupConfig) -> BackupRepo {
+        let guild = Guild::new(Arc::new(FakeDiscord::new()), GUILD_ID);
+        BackupRepo::new(guild, Arc::new(Database::default()), &config)
+    }
+
+    #[tokio::test]
+    async fn keeps_latest_snapshots() {
+        let backup_repo = backup_repo();
+        let mut names = vec![];
+        for _ in 0..5 {
+            names.push(backup_repo.snapshot().await.unwrap());
+        }
+
+        // 같은 시각에 찍혀도 이름이 겹치지 않음
+        let mut unique = names.clone();
+        unique.dedup();
+        assert_eq!(unique.len(), 5);
+
+        let latest: Vec<String> = names.into_iter().rev().take(3).collect();
+        assert_eq!(backup_repo.get_snapshots().await.unwrap(), latest);
+        backup_repo.get_snapshot(&latest[0]).await.unwrap();
+        _ = tokio::fs::remove_dir_all(&backup_repo.path).await;
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_name() {
+        let backup_repo = backup_repo();
+        assert!(backup_repo.get_snapshot("../secret").await.is_err());
+        assert!(backup_repo.get_snapshot("").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn encrypts_snapshots() {
+        let config = BackupConfig { codec: Codec::Deflate, secrets: vec![String::from("secret")], ..backup_config() };
+        let backup_repo = backup_repo_with(config.clone());
+        let name = backup_repo.snapshot().await.unwrap();
+
+        let payload = tokio::fs::read_to_string(backup_repo.snapshot_path(&name)).await.unwrap();
+        assert!(is_encrypted(&payload));
+        assert_eq!(backup_repo.get_snapshot(&name).await.unwrap().teams.len(), 4);
+
+        // 키가 없으면 읽을 수 없음
+        let without_key = backup_repo_with(BackupConfig { secrets: vec![], ..config });
+        assert!(without_key.get_snapshot(&name).await.is_err());
+        _ = tokio::fs::remove_dir_all(&backup_repo.path).await;
+    }
+
+    #[tokio::test]
+    async fn reads_plain_json_snapshot() {
+        let backup_repo = backup_repo_with(BackupConfig { secrets: vec![String::from("secret")], ..backup_config() });
+        tokio::fs::create_dir_all(&backup_repo.path).await.unwrap();
+        let json = serenity::json::to_string(&Entity::empty()).unwrap();
+        tokio::fs::write(backup_repo.snapshot_path("20240101-000000-000000"), json).await.unwrap();
+
+        assert_eq!(backup_repo.get_snapshot("20240101-000000-000000").await.unwrap().teams.len(), 4);
+        _ = tokio::fs::remove_dir_all(&backup_repo.path).await;
+    }
+}
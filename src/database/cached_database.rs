@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use serenity::all::{ChannelId, GuildId};
+use serenity::async_trait;
+use serenity::Result;
+
+use crate::database::database::DatabaseTrait;
+use crate::entity::entity::Entity;
+use crate::global::discord::Guild;
+
+// guild 별 Entity를 메모리에 들고 있다가, 쓰기 때는 갱신하고 외부에서 수정되면 버립니다
+pub struct CachedDatabase {
+    inner: Arc<dyn DatabaseTrait>,
+    entities: RwLock<HashMap<GuildId, Entity>>,
+}
+
+impl CachedDatabase {
+    pub fn new(inner: Arc<dyn DatabaseTrait>) -> Self {
+        CachedDatabase { inner, entities: RwLock::new(HashMap::new()) }
+    }
+}
+
+#[async_trait]
+impl DatabaseTrait for CachedDatabase {
+    async fn get_entity(&self, guild: &Guild) -> Result<Entity> {
+        if let Some(entity) = self.entities.read().unwrap().get(&guild.guild_id) {
+            return Ok(entity.clone());
+        }
+        let entity = self.inner.get_entity(guild).await?;
+        self.entities.write().unwrap().insert(guild.guild_id, entity.clone());
+        Ok(entity)
+    }
+
+    async fn edit_entity(&self, guild: &Guild, entity: &Entity) -> Result<()> {
+        match self.inner.edit_entity(guild, entity).await {
+            Ok(()) => {
+                let mut entity = entity.clone();
+                entity.revision += 1;
+                self.entities.write().unwrap().insert(guild.guild_id, entity);
+                Ok(())
+            }
+            Err(why) => {
+                // 충돌 등으로 실패하면 캐시가 오래됐을 수 있으므로 버림
+                self.invalidate(guild.guild_id);
+                Err(why)
+            }
+        }
+    }
+
+    async fn init_entity(&self, guild: &Guild) -> Result<()> {
+        self.invalidate(guild.guild_id);
+        self.inner.init_entity(guild).await
+    }
+
+    fn invalidate(&self, guild_id: GuildId) {
+        self.entities.write().unwrap().remove(&guild_id);
+    }
+
+    fn is_database_channel(&self, guild_id: GuildId, channel_id: ChannelId) -> bool {
+        self.inner.is_database_channel(guild_id, channel_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::database::cached_database::CachedDatabase;
+    use crate::database::database::{Database, DatabaseTrait};
+    use crate::entity::team::Team;
+    use crate::global::discord::Guild;
+    use crate::global::fake_discord::{FakeDiscord, GUILD_ID};
+
+    fn setup() -> (Arc<FakeDiscord>, Guild, Arc<Database>, CachedDatabase) {
+        let api = Arc::new(FakeDiscord::new());
+        let guild = Guild::new(api.clone(), GUILD_ID);
+        let inner = Arc::new(Database::default());
+        let cached = CachedDatabase::new(inner.clone());
+        (api, guild, inner, cached)
+    }
+
+    // 캐시를 거치지 않고 저장소를 직접 수정
+    async fn edit_outside(inner: &Database, guild: &Guild) {
+        let mut entity = inner.get_entity(guild).await.unwrap();
+        entity.teams.push(Team { name: String::from("outside") });
+        inner.edit_entity(guild, &entity).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn returns_cached_entity_until_invalidated() {
+        let (_api, guild, inner, cached) = setup();
+        assert_eq!(cached.get_entity(&guild).await.unwrap().revision, 0);
+        edit_outside(&inner, &guild).await;
+
+        assert_eq!(cached.get_entity(&guild).await.unwrap().revision, 0);
+        cached.invalidate(GUILD_ID);
+        assert_eq!(cached.get_entity(&guild).await.unwrap().revision, 1);
+    }
+
+    #[tokio::test]
+    async fn invalidates_on_failed_edit() {
+        let (_api, guild, inner, cached) = setup();
+        let stale = cached.get_entity(&guild).await.unwrap();
+        edit_outside(&inner, &guild).await;
+
+        assert!(cached.edit_entity(&guild, &stale).await.is_err());
+        let entity = cached.get_entity(&guild).await.unwrap();
+        assert_eq!(entity.revision, 1);
+        assert_eq!(entity.teams.last().unwrap().name, "outside");
+    }
+
+    #[tokio::test]
+    async fn invalidates_on_init() {
+        let (_api, guild, _inner, cached) = setup();
+        let mut entity = cached.get_entity(&guild).await.unwrap();
+        entity.teams.clear();
+        cached.edit_entity(&guild, &entity).await.unwrap();
+        assert!(cached.get_entity(&guild).await.unwrap().teams.is_empty());
+
+        cached.init_entity(&guild).await.unwrap();
+        assert_eq!(cached.get_entity(&guild).await.unwrap().teams.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn caches_next_revision_after_edit() {
+        let (api, guild, _inner, cached) = setup();
+        let mut entity = cached.get_entity(&guild).await.unwrap();
+        entity.teams.push(Team { name: String::from("QA") });
+        cached.edit_entity(&guild, &entity).await.unwrap();
+
+        let entity = cached.get_entity(&guild).await.unwrap();
+        assert_eq!(entity.revision, 1);
+        assert_eq!(entity.revision, api.stored_entity().revision);
+        assert_eq!(entity.teams.last().unwrap().name, "QA");
+
+        // 캐시된 revision으로 바로 다시 쓸 수 있음
+        cached.edit_entity(&guild, &entity).await.unwrap();
+        assert_eq!(api.stored_entity().revision, 2);
+    }
+}
@@ -1,74 +1,299 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
 use serenity;
-use serenity::{all::GetMessages, async_trait, Error};
-use serenity::all::{EditMessage, GuildChannel, Message};
+use serenity::{async_trait, Error};
+use serenity::all::{ChannelId, ChannelType, CreateChannel, CreateMessage, GuildChannel, GuildId, Message, MessageId, PermissionOverwrite, PermissionOverwriteType, Permissions, RoleId};
 use serenity::Result;
 
+use crate::config::config::{Codec, Config, DatabaseBackend};
+use crate::database::cached_database::CachedDatabase;
+use crate::database::file_database::FileDatabase;
+use crate::database::shard;
+use crate::database::transaction::{lock_unless_held, next_revision};
 use crate::entity::entity::Entity;
 use crate::global::discord::{Guild};
-use crate::util::json::to_string;
+use crate::util::crypto::{decrypt, encrypt, is_encrypted};
+use crate::util::json::{decode, encode, to_string};
 
-const DATABASE_CHANNEL: &str = "database-v1";
+pub const DATABASE_CHANNEL: &str = "database-v1";
+const MESSAGES_PER_PAGE: u8 = 100;
 
-pub struct Database;
+#[derive(Default)]
+pub struct Database {
+    codec: Codec,
+    // 첫 번째 키로 암호화하고, 모든 키로 복호화를 시도합니다
+    secrets: Vec<String>,
+    // guild 별로 찾은 데이터베이스 채널, 이벤트가 이 채널에서 온 것인지 확인할 때 사용
+    channel_ids: RwLock<HashMap<GuildId, ChannelId>>,
+}
 
 #[async_trait]
 pub trait DatabaseTrait: Send + Sync {
     async fn get_entity(&self, guild: &Guild) -> Result<Entity>;
     async fn edit_entity(&self, guild: &Guild, entity: &Entity) -> Result<()>;
     async fn init_entity(&self, guild: &Guild) -> Result<()>;
+
+    // 저장소가 외부에서 수정됐을 때 호출됩니다
+    fn invalidate(&self, _guild_id: GuildId) {}
+
+    // 저장에 쓰는 디스코드 채널인지, 아직 읽거나 쓴 적이 없는 guild라면 false
+    fn is_database_channel(&self, _guild_id: GuildId, _channel_id: ChannelId) -> bool {
+        false
+    }
+}
+
+pub fn create_database(config: &Config) -> Arc<dyn DatabaseTrait> {
+    match config.database_backend {
+        DatabaseBackend::Discord => Arc::new(CachedDatabase::new(Arc::new(Database::new(config.database_codec, config.database_secrets.clone())))),
+        DatabaseBackend::File => Arc::new(FileDatabase::new(&config.database_path)),
+    }
+}
+
+// json -> codec -> 암호화(secret이 있을 때) 순서, 첫 번째 secret으로 암호화합니다
+pub fn encode_entity(entity: &Entity, codec: Codec, secrets: &[String]) -> Result<String> {
+    let payload = encode(&to_string(entity)?, codec)?;
+    match secrets.first() {
+        Some(secret) => encrypt(&payload, secret),
+        None => Ok(payload)
+    }
+}
+
+pub fn decode_entity(payload: &str, secrets: &[String]) -> Result<Entity> {
+    let payload = if is_encrypted(payload) {
+        decrypt(payload, secrets)?
+    } else {
+        payload.to_string()
+    };
+    Entity::from_json(&decode(&payload)?)
+}
+
+impl Database {
+    pub fn new(codec: Codec, secrets: Vec<String>) -> Self {
+        Database { codec, secrets, ..Default::default() }
+    }
+
+    // 채널이 없으면 봇만 쓸 수 있는 채널을 새로 만듭니다
+    async fn get_database_channel(&self, guild: &Guild) -> Result<GuildChannel> {
+        let channels = guild.api.get_channels(guild.guild_id).await?;
+        if let Some(channel) = channels.into_iter().find(|channel| channel.name == DATABASE_CHANNEL) {
+            self.channel_ids.write().unwrap().insert(guild.guild_id, channel.id);
+            return Ok(channel);
+        }
+
+        let bot_id = guild.api.get_bot_id().await?;
+        let permissions = vec![
+            // @everyone 역할의 id는 guild id와 같음
+            PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::SEND_MESSAGES | Permissions::SEND_MESSAGES_IN_THREADS | Permissions::CREATE_PUBLIC_THREADS,
+                kind: PermissionOverwriteType::Role(RoleId::new(guild.guild_id.get())),
+            },
+            PermissionOverwrite {
+                allow: Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES | Permissions::READ_MESSAGE_HISTORY | Permissions::MANAGE_MESSAGES,
+                deny: Permissions::empty(),
+                kind: PermissionOverwriteType::Member(bot_id),
+            },
+        ];
+        let builder = CreateChannel::new(DATABASE_CHANNEL)
+            .kind(ChannelType::Text)
+            .topic("mowgli 데이터베이스 채널입니다. 메세지를 수정하거나 삭제하지 마세요.")
+            .permissions(permissions);
+        let channel = guild.api.create_channel(guild.guild_id, builder).await?;
+        println!("{} 데이터베이스 채널 생성", guild.guild_id);
+        self.channel_ids.write().unwrap().insert(guild.guild_id, channel.id);
+        Ok(channel)
+    }
+
+    fn parse_entity(&self, messages: &[Message]) -> Result<Entity> {
+        let contents: Vec<&str> = messages.iter().map(|message| message.content.as_str()).collect();
+
+        // 조각으로 나누기 전에 저장된 단일 메세지 호환
+        if !contents.iter().any(|content| shard::is_chunk(content)) {
+            let content = contents.last()
+                .ok_or_else(|| Error::Other("데이터베이스 메세지를 찾을 수 없습니다"))?;
+            return Entity::from_json(content);
+        }
+
+        decode_entity(&shard::join(&contents)?, &self.secrets)
+    }
+
+    // 새 세대의 조각을 모두 보낸 뒤에 이전 메세지들을 삭제합니다
+    // 중간에 실패해도 이전 세대가 남아 있어서 get_entity는 마지막으로 완성된 세대를 읽음
+    async fn write_entity(&self, guild: &Guild, channel: &GuildChannel, messages: &[Message], entity: &Entity) -> Result<()> {
+        let generation = messages.iter()
+            .filter_map(|message| shard::generation(&message.content))
+            .max()
+            .unwrap_or(0) + 1;
+        for chunk in shard::split(&encode_entity(entity, self.codec, &self.secrets)?, generation) {
+            guild.api.send_message(channel.id, CreateMessage::new().content(chunk)).await?;
+        }
+        for message in messages {
+            guild.api.delete_message(channel.id, message.id).await?;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
 impl DatabaseTrait for Database {
     async fn get_entity(&self, guild: &Guild) -> Result<Entity> {
-        let message = get_database_message(guild).await?;
-        serenity::json::from_str(&message.content)
+        let channel = self.get_database_channel(guild).await?;
+        let messages = get_database_messages(guild, &channel).await?;
+
+        // 처음 사용하는 guild라면 빈 Entity로 초기화
+        // 동시에 처음 읽어 조각이 두 벌 저장되지 않도록 lock을 잡고 다시 확인
+        if messages.is_empty() {
+            let _guard = lock_unless_held(guild.guild_id).await;
+            let messages = get_database_messages(guild, &channel).await?;
+            if !messages.is_empty() {
+                return self.parse_entity(&messages);
+            }
+            let entity = Entity::empty();
+            self.write_entity(guild, &channel, &messages, &entity).await?;
+            return Ok(entity);
+        }
+        self.parse_entity(&messages)
     }
 
     async fn edit_entity(&self, guild: &Guild, entity: &Entity) -> Result<()> {
-        let mut message = get_database_message(guild).await?;
-        let json = to_string(entity)?;
-        let builder = EditMessage::new().content(json);
-        message.edit(&guild.http, builder).await?;
-        Ok(())
+        let channel = self.get_database_channel(guild).await?;
+        let messages = get_database_messages(guild, &channel).await?;
+        let entity = next_revision(&self.parse_entity(&messages)?, entity)?;
+        self.write_entity(guild, &channel, &messages, &entity).await
     }
 
     async fn init_entity(&self, guild: &Guild) -> Result<()> {
-        let http = &guild.http;
-        let channel = get_database_channel(guild).await?;
+        let channel = self.get_database_channel(guild).await?;
+        let messages = get_database_messages(guild, &channel).await?;
+        self.write_entity(guild, &channel, &messages, &Entity::empty()).await
+    }
 
-        // delete all messages
-        let builder = GetMessages::new()
-            .limit(100);
-        let messages = channel.messages(http, builder).await?;
-        for message in messages {
-            message.delete(http).await?
+    fn is_database_channel(&self, guild_id: GuildId, channel_id: ChannelId) -> bool {
+        self.channel_ids.read().unwrap().get(&guild_id) == Some(&channel_id)
+    }
+}
+
+// 봇이 쓴 데이터베이스 메세지들을 오래된 순으로, 다른 사람이 쓴 메세지는 무시합니다
+async fn get_database_messages(guild: &Guild, channel: &GuildChannel) -> Result<Vec<Message>> {
+    let bot_id = guild.api.get_bot_id().await?;
+    let mut messages = vec![];
+    let mut before: Option<MessageId> = None;
+    loop {
+        let page = guild.api.get_messages(channel.id, before, MESSAGES_PER_PAGE).await?;
+        before = page.iter().map(|message| message.id).min();
+        let is_last_page = page.len() < MESSAGES_PER_PAGE as usize;
+        messages.extend(page.into_iter().filter(|message| message.author.id == bot_id));
+        if is_last_page {
+            break;
         }
+    }
 
-        // create empty entity
-        let entity = Entity::empty();
-        let message = to_string(&entity)?;
-        channel.say(http, message).await?;
-        Ok(())
+    // 조각이 하나라도 있으면 조각만, 없으면 이전 형식의 단일 메세지
+    if messages.iter().any(|message| shard::is_chunk(&message.content)) {
+        messages.retain(|message| shard::is_chunk(&message.content));
+    } else {
+        messages.retain(|message| message.content.starts_with('{'));
     }
+    messages.sort_by_key(|message| message.id);
+    Ok(messages)
 }
 
-async fn get_database_channel(guild: &Guild) -> Result<GuildChannel> {
-    let channels = guild.guild_id.channels(&guild.http).await?;
-    let channel = channels.iter()
-        .find(|(_, channel)| channel.name == DATABASE_CHANNEL)
-        .map(|(_, channel)| channel)
-        .ok_or_else(|| Error::Other("채널을 찾을 수 없습니다"))?;
-    Ok(channel.clone())
-}
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
 
-async fn get_database_message(guild: &Guild) -> Result<Message> {
-    let channel = get_database_channel(guild).await?;
-    let builder = GetMessages::new().limit(100);
-    let messages = channel.messages(&guild.http, builder).await?;
+    use serenity::all::ChannelId;
 
-    let message = messages.get(0)
-        .ok_or_else(|| Error::Other("데이터베이스 메세지를 찾을 수 없습니다"))?;
+    use crate::config::config::Codec;
+    use crate::database::database::{Database, DatabaseTrait, DATABASE_CHANNEL};
+    use crate::database::transaction::update_entity;
+    use crate::entity::team::Team;
+    use crate::global::discord::Guild;
+    use crate::global::fake_discord::{FakeDiscord, GUILD_ID};
+
+    #[tokio::test]
+    async fn remembers_database_channel() {
+        let api = Arc::new(FakeDiscord::new());
+        let other_channel_id = api.add_channel("general");
+        let database = Database::default();
+        assert!(!database.is_database_channel(GUILD_ID, ChannelId::new(1)));
+
+        database.get_entity(&Guild::new(api.clone(), GUILD_ID)).await.unwrap();
+        let channel_id = api.find_channel(DATABASE_CHANNEL).unwrap();
+        assert!(database.is_database_channel(GUILD_ID, channel_id));
+        assert!(!database.is_database_channel(GUILD_ID, other_channel_id));
+    }
 
-    Ok(message.clone())
-}
\ No newline at end of file
+    #[tokio::test]
+    async fn creates_entity_once() {
+        let api = Arc::new(FakeDiscord::new());
+        let guild = Guild::new(api.clone(), GUILD_ID);
+        let database = Database::default();
+
+        let (first, second) = tokio::join!(database.get_entity(&guild), database.get_entity(&guild));
+        first.unwrap();
+        second.unwrap();
+        assert_eq!(api.message_contents(api.find_channel(DATABASE_CHANNEL).unwrap()).len(), 1);
+
+        // 이미 guild lock을 잡은 쓰기에서 처음 읽어도 기다리지 않음
+        let guild = Guild::new(Arc::new(FakeDiscord::new()), GUILD_ID);
+        let entity = update_entity(&database, &guild, |_| Ok(())).await.unwrap();
+        assert_eq!(entity.revision, 1);
+    }
+
+    #[tokio::test]
+    async fn writes_with_codec() {
+        let api = Arc::new(FakeDiscord::new());
+        let guild = Guild::new(api.clone(), GUILD_ID);
+        let database = Database::new(Codec::Deflate, vec![]);
+        database.get_entity(&guild).await.unwrap();
+
+        let contents = api.message_contents(api.find_channel(DATABASE_CHANNEL).unwrap());
+        assert!(contents[0].lines().nth(1).unwrap().starts_with('D'));
+        assert_eq!(database.get_entity(&guild).await.unwrap().teams.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn reads_with_old_secret() {
+        let api = Arc::new(FakeDiscord::new());
+        let guild = Guild::new(api.clone(), GUILD_ID);
+        Database::new(Codec::Json, vec![String::from("old")]).get_entity(&guild).await.unwrap();
+
+        let database = Database::new(Codec::Json, vec![String::from("new"), String::from("old")]);
+        let entity = database.get_entity(&guild).await.unwrap();
+        database.edit_entity(&guild, &entity).await.unwrap();
+        assert!(Database::new(Codec::Json, vec![String::from("new")]).get_entity(&guild).await.is_ok());
+        assert!(Database::new(Codec::Json, vec![String::from("old")]).get_entity(&guild).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn keeps_previous_generation_on_failed_write() {
+        let api = Arc::new(FakeDiscord::new());
+        let guild = Guild::new(api.clone(), GUILD_ID);
+        let database = Database::default();
+        let mut entity = database.get_entity(&guild).await.unwrap();
+        entity.teams = (0..200).map(|index| Team { name: format!("team-{index}") }).collect();
+        database.edit_entity(&guild, &entity).await.unwrap();
+        let channel_id = api.find_channel(DATABASE_CHANNEL).unwrap();
+        let chunk_count = api.message_contents(channel_id).len();
+        assert!(chunk_count > 1);
+
+        // 새 세대의 첫 조각만 보내고 실패하면 이전 세대를 그대로 읽음
+        api.fail_sends_after(1);
+        let mut entity = database.get_entity(&guild).await.unwrap();
+        entity.teams = (0..200).map(|index| Team { name: format!("new-team-{index}") }).collect();
+        assert!(database.edit_entity(&guild, &entity).await.is_err());
+        assert_eq!(api.message_contents(channel_id).len(), chunk_count + 1);
+        let entity = database.get_entity(&guild).await.unwrap();
+        assert_eq!((entity.revision, entity.teams[0].name.as_str()), (1, "team-0"));
+
+        // 다음 쓰기는 남은 조각까지 모두 정리
+        api.fail_sends_after(usize::MAX);
+        database.edit_entity(&guild, &entity).await.unwrap();
+        let contents = api.message_contents(channel_id);
+        assert_eq!(contents.len(), chunk_count);
+        assert!(contents.iter().all(|content| content.starts_with("#db 4 ")));
+        assert_eq!(api.stored_entity().revision, 2);
+    }
+}
@@ -1,22 +1,45 @@
-use crate::database::database::{Database, DatabaseTrait};
+use std::sync::Arc;
+use crate::database::database::DatabaseTrait;
+use crate::database::transaction::{replace_entity, undo_entity};
 use crate::entity::entity::Entity;
 use crate::global::discord::{Guild};
+use crate::util::json::to_string;
 
 pub struct DatabaseRepo {
-    guild: Guild
+    guild: Guild,
+    database: Arc<dyn DatabaseTrait>,
 }
 
 impl DatabaseRepo {
-    pub fn new(guild: Guild) -> Self {
-        DatabaseRepo { guild }
+    pub fn new(guild: Guild, database: Arc<dyn DatabaseTrait>) -> Self {
+        DatabaseRepo { guild, database }
     }
 
-    pub async fn force_import(&self, json: &str) -> serenity::Result<()> {
-        let entity: Entity = serenity::json::from_str(json)?;
-        Database.edit_entity(&self.guild, &entity).await
+    pub async fn get(&self) -> serenity::Result<Entity> {
+        self.database.get_entity(&self.guild).await
+    }
+
+    pub async fn force_import(&self, entity: Entity) -> serenity::Result<()> {
+        replace_entity(self.database.as_ref(), &self.guild, entity).await?;
+        Ok(())
+    }
+
+    // pretty가 없으면 IS_JSON_PRETTY 설정을 따름
+    pub async fn export(&self, pretty: Option<bool>) -> serenity::Result<String> {
+        let entity = self.database.get_entity(&self.guild).await?;
+        match pretty {
+            Some(true) => serenity::json::to_string_pretty(&entity),
+            Some(false) => serenity::json::to_string(&entity),
+            None => to_string(&entity)
+        }
+    }
+
+    pub async fn undo(&self, is_admin: bool) -> serenity::Result<Entity> {
+        undo_entity(self.database.as_ref(), &self.guild, is_admin).await
     }
 
     pub async fn reset(&self) -> serenity::Result<()> {
-        Database.init_entity(&self.guild).await
+        replace_entity(self.database.as_ref(), &self.guild, Entity::empty()).await?;
+        Ok(())
     }
-}
\ No newline at end of file
+}
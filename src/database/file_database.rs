@@ -0,0 +1,91 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use serenity::async_trait;
+use serenity::Result;
+
+use crate::database::database::DatabaseTrait;
+use crate::database::transaction::next_revision;
+use crate::entity::entity::Entity;
+use crate::global::discord::Guild;
+use crate::util::json::to_string;
+
+// guild 별로 `{path}/{guild_id}.json` 파일에 Entity를 저장합니다.
+pub struct FileDatabase {
+    path: PathBuf,
+}
+
+impl FileDatabase {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileDatabase { path: path.into() }
+    }
+
+    fn entity_path(&self, guild: &Guild) -> PathBuf {
+        self.path.join(format!("{}.json", guild.guild_id))
+    }
+
+    async fn write(&self, guild: &Guild, entity: &Entity) -> Result<()> {
+        tokio::fs::create_dir_all(&self.path).await?;
+
+        // 쓰는 도중 종료돼도 기존 파일이 깨지지 않도록 임시 파일에 쓴 뒤 교체
+        let path = self.entity_path(guild);
+        let temp_path = path.with_extension("json.tmp");
+        tokio::fs::write(&temp_path, to_string(entity)?).await?;
+        tokio::fs::rename(&temp_path, &path).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl DatabaseTrait for FileDatabase {
+    async fn get_entity(&self, guild: &Guild) -> Result<Entity> {
+        let json = match tokio::fs::read_to_string(self.entity_path(guild)).await {
+            Ok(v) => v,
+            // 처음 사용하는 guild라면 빈 Entity로 초기화
+            Err(why) if why.kind() == ErrorKind::NotFound => {
+                let entity = Entity::empty();
+                self.write(guild, &entity).await?;
+                return Ok(entity);
+            }
+            Err(why) => Err(why)?
+        };
+        Entity::from_json(&json)
+    }
+
+    async fn edit_entity(&self, guild: &Guild, entity: &Entity) -> Result<()> {
+        let current = self.get_entity(guild).await?;
+        let entity = next_revision(&current, entity)?;
+        self.write(guild, &entity).await
+    }
+
+    async fn init_entity(&self, guild: &Guild) -> Result<()> {
+        self.write(guild, &Entity::empty()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::database::database::DatabaseTrait;
+    use crate::database::file_database::FileDatabase;
+    use crate::database::transaction::update_entity;
+    use crate::global::discord::Guild;
+    use crate::global::fake_discord::{FakeDiscord, GUILD_ID};
+
+    #[tokio::test]
+    async fn creates_entity_on_first_read() {
+        let path = std::env::temp_dir().join(format!("mowgli-file-database-{}", std::process::id()));
+        let database = FileDatabase::new(&path);
+        let guild = Guild::new(Arc::new(FakeDiscord::new()), GUILD_ID);
+
+        let entity = update_entity(&database, &guild, |entity| {
+            entity.meta_data.timezone = Some(String::from("Asia/Tokyo"));
+            Ok(())
+        }).await.unwrap();
+        assert_eq!(entity.revision, 1);
+        assert_eq!(database.get_entity(&guild).await.unwrap().meta_data.timezone.as_deref(), Some("Asia/Tokyo"));
+
+        _ = tokio::fs::remove_dir_all(&path).await;
+    }
+}
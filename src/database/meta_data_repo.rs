@@ -1,27 +1,79 @@
+use std::sync::Arc;
+use chrono_tz::Tz;
 use serenity::Error;
-use crate::database::database::{Database, DatabaseTrait};
-use crate::entity::metadata::ChannelId;
+use crate::database::database::DatabaseTrait;
+use crate::database::transaction::update_entity;
+use crate::entity::metadata::{ChannelId, DEFAULT_TIMEZONE};
 use crate::global::discord::{Guild};
 
 pub struct MetaDataRepo {
     guild: Guild,
+    database: Arc<dyn DatabaseTrait>,
 }
 
 impl MetaDataRepo {
-    pub fn new(guild: Guild) -> Self {
-        MetaDataRepo { guild }
+    pub fn new(guild: Guild, database: Arc<dyn DatabaseTrait>) -> Self {
+        MetaDataRepo { guild, database }
     }
 
     pub async fn get_main_channel(&self) -> serenity::Result<ChannelId> {
-        let entity = Database.get_entity(&self.guild).await?;
+        let entity = self.database.get_entity(&self.guild).await?;
         let channel_id = entity.meta_data.main_channel_id.ok_or_else(|| Error::Other("channel_id를 찾을 수 없습니다"))?;
         Ok(channel_id)
     }
 
     pub async fn edit_main_channel(&self, channel_id: ChannelId) -> serenity::Result<()> {
-        let mut entity = Database.get_entity(&self.guild).await?;
-        entity.meta_data.main_channel_id = Some(channel_id);
-        Database.edit_entity(&self.guild, &entity).await?;
+        update_entity(self.database.as_ref(), &self.guild, |entity| {
+            entity.meta_data.main_channel_id = Some(channel_id);
+            Ok(())
+        }).await?;
         Ok(())
     }
-}
\ No newline at end of file
+
+    pub async fn edit_log_channel(&self, channel_id: Option<ChannelId>) -> serenity::Result<()> {
+        update_entity(self.database.as_ref(), &self.guild, |entity| {
+            entity.meta_data.log_channel_id = channel_id;
+            Ok(())
+        }).await?;
+        Ok(())
+    }
+
+    pub async fn get_timezone(&self) -> serenity::Result<Tz> {
+        let entity = self.database.get_entity(&self.guild).await?;
+        let timezone = match entity.meta_data.timezone {
+            Some(timezone) => timezone.parse().map_err(|_| Error::Other("저장된 시간대가 올바르지 않습니다"))?,
+            None => DEFAULT_TIMEZONE
+        };
+        Ok(timezone)
+    }
+
+    pub async fn edit_timezone(&self, timezone: Tz) -> serenity::Result<()> {
+        update_entity(self.database.as_ref(), &self.guild, |entity| {
+            entity.meta_data.timezone = Some(timezone.name().to_string());
+            Ok(())
+        }).await?;
+        Ok(())
+    }
+
+    // 투두 스레드를 만들 채널, 팀 채널이 없으면 메인 채널
+    pub async fn get_thread_channel(&self, team_name: &str) -> serenity::Result<ChannelId> {
+        let entity = self.database.get_entity(&self.guild).await?;
+        entity.meta_data.team_channels.get(team_name).copied()
+            .or(entity.meta_data.main_channel_id)
+            .ok_or_else(|| Error::Other("팀 채널이나 메인 채널을 먼저 설정해주세요"))
+    }
+
+    pub async fn edit_team_channel(&self, team_name: &str, channel_id: Option<ChannelId>) -> serenity::Result<()> {
+        update_entity(self.database.as_ref(), &self.guild, |entity| {
+            if !entity.teams.iter().any(|team| team.name == team_name) {
+                Err(Error::Other("팀을 찾을 수 없습니다"))?
+            }
+            match channel_id {
+                Some(channel_id) => entity.meta_data.team_channels.insert(team_name.to_string(), channel_id),
+                None => entity.meta_data.team_channels.remove(team_name),
+            };
+            Ok(())
+        }).await?;
+        Ok(())
+    }
+}
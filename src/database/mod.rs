@@ -4,3 +4,10 @@ pub mod todo_repo;
 pub mod meta_data_repo;
 pub mod database_repo;
 pub mod schedule_repo;
+pub mod file_database;
+pub mod shard;
+pub mod transaction;
+pub mod cached_database;
+pub mod backup_repo;
+pub mod audit_repo;
+pub mod undo;
@@ -1,20 +1,29 @@
-use crate::database::database::{Database, DatabaseTrait};
+use std::sync::Arc;
+use crate::database::database::DatabaseTrait;
+use crate::database::transaction::update_entity;
 use crate::entity::schedule::Schedule;
 use crate::global::discord::Guild;
 
 pub struct ScheduleRepo {
     guild: Guild,
+    database: Arc<dyn DatabaseTrait>,
 }
 
 impl ScheduleRepo {
-    async fn create_schedule(&self, schedule: Schedule) -> serenity::Result<()> {
-        let mut entity = Database.get_entity(&self.guild).await?;
-        entity.schedules.push(schedule);
-        Database.edit_entity(&self.guild, &entity).await
+    pub fn new(guild: Guild, database: Arc<dyn DatabaseTrait>) -> Self {
+        ScheduleRepo { guild, database }
     }
 
-    async fn get_schedule(&self) -> serenity::Result<Vec<Schedule>> {
-        let entity = Database.get_entity(&self.guild).await?;
+    pub async fn create_schedule(&self, schedule: Schedule) -> serenity::Result<()> {
+        update_entity(self.database.as_ref(), &self.guild, |entity| {
+            entity.schedules.push(schedule.clone());
+            Ok(())
+        }).await?;
+        Ok(())
+    }
+
+    pub async fn get_schedule(&self) -> serenity::Result<Vec<Schedule>> {
+        let entity = self.database.get_entity(&self.guild).await?;
         Ok(entity.schedules)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serenity::Error;
+
+// 디스코드 메세지 최대 길이는 2000자, 헤더를 위한 여유를 남겨둠
+const CHUNK_SIZE: usize = 1900;
+const HEADER_PREFIX: &str = "#db ";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ShardError {
+    // 조각이 하나도 없음
+    Empty,
+    // 같은 세대인데 조각의 개수가 다름
+    TotalMismatch { generation: u64 },
+    Missing { generation: u64, index: usize, total: usize },
+    // 같은 번호의 조각이 서로 다른 내용으로 여러 개
+    Duplicated { generation: u64, index: usize },
+}
+
+impl fmt::Display for ShardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShardError::Empty => write!(f, "데이터베이스 메세지를 찾을 수 없습니다"),
+            ShardError::TotalMismatch { generation } => write!(f, "데이터베이스 {generation}세대 조각의 개수가 일치하지 않습니다"),
+            ShardError::Missing { generation, index, total } => write!(f, "데이터베이스 {generation}세대 조각 {index}/{total}을 찾을 수 없습니다"),
+            ShardError::Duplicated { generation, index } => write!(f, "데이터베이스 {generation}세대 조각 {index}이 서로 다른 내용으로 중복됐습니다"),
+        }
+    }
+}
+
+impl std::error::Error for ShardError {}
+
+// 저장소 계층의 다른 에러처럼 Error::Other로 전달합니다
+impl From<ShardError> for Error {
+    fn from(why: ShardError) -> Self {
+        match why {
+            ShardError::Empty => Error::Other("데이터베이스 메세지를 찾을 수 없습니다"),
+            ShardError::TotalMismatch { .. } => Error::Other("데이터베이스 조각의 개수가 일치하지 않습니다"),
+            ShardError::Missing { .. } => Error::Other("데이터베이스 조각을 찾을 수 없습니다"),
+            ShardError::Duplicated { .. } => Error::Other("데이터베이스 조각이 서로 다른 내용으로 중복됐습니다"),
+        }
+    }
+}
+
+struct Chunk<'a> {
+    generation: u64,
+    index: usize,
+    total: usize,
+    body: &'a str,
+}
+
+// payload를 `#db {generation} {index}/{total}` 헤더가 붙은 메세지 내용들로 나눕니다
+// 쓰기마다 generation을 올려서, 쓰다가 실패해도 이전 세대의 조각으로 읽을 수 있게 합니다
+pub fn split(payload: &str, generation: u64) -> Vec<String> {
+    let chars: Vec<char> = payload.chars().collect();
+    let chunks: Vec<String> = if chars.is_empty() {
+        vec![String::new()]
+    } else {
+        chars.chunks(CHUNK_SIZE).map(|chunk| chunk.iter().collect()).collect()
+    };
+    let total = chunks.len();
+    chunks.iter()
+        .enumerate()
+        .map(|(index, chunk)| format!("{HEADER_PREFIX}{generation} {}/{total}\n{chunk}", index + 1))
+        .collect()
+}
+
+pub fn is_chunk(content: &str) -> bool {
+    parse_chunk(content).is_some()
+}
+
+pub fn generation(content: &str) -> Option<u64> {
+    parse_chunk(content).map(|chunk| chunk.generation)
+}
+
+// 조각이 모두 있는 가장 최신 세대의 내용을 다시 합칩니다
+pub fn join(contents: &[&str]) -> Result<String, ShardError> {
+    let mut generations: BTreeMap<u64, Vec<Chunk>> = BTreeMap::new();
+    for chunk in contents.iter().filter_map(|content| parse_chunk(content)) {
+        generations.entry(chunk.generation).or_default().push(chunk);
+    }
+
+    let mut newest_error = None;
+    for chunks in generations.into_values().rev() {
+        match join_generation(chunks) {
+            Ok(payload) => return Ok(payload),
+            Err(why) => {
+                newest_error.get_or_insert(why);
+            }
+        }
+    }
+    Err(newest_error.unwrap_or(ShardError::Empty))
+}
+
+fn join_generation(mut chunks: Vec<Chunk>) -> Result<String, ShardError> {
+    let (generation, total) = match chunks.first() {
+        Some(chunk) => (chunk.generation, chunk.total),
+        None => return Err(ShardError::Empty)
+    };
+    if chunks.iter().any(|chunk| chunk.total != total) {
+        return Err(ShardError::TotalMismatch { generation });
+    }
+
+    chunks.sort_by_key(|chunk| chunk.index);
+    for pair in chunks.windows(2) {
+        if pair[0].index == pair[1].index && pair[0].body != pair[1].body {
+            return Err(ShardError::Duplicated { generation, index: pair[0].index });
+        }
+    }
+    chunks.dedup_by_key(|chunk| chunk.index);
+    if let Some(index) = (1..=total).find(|index| chunks.get(index - 1).is_none_or(|chunk| chunk.index != *index)) {
+        return Err(ShardError::Missing { generation, index, total });
+    }
+
+    Ok(chunks.iter().map(|chunk| chunk.body).collect())
+}
+
+// 세대가 없는 `#db {index}/{total}`은 세대를 나누기 전에 저장된 0세대
+fn parse_chunk(content: &str) -> Option<Chunk<'_>> {
+    let rest = content.strip_prefix(HEADER_PREFIX)?;
+    let (header, body) = rest.split_once('\n')?;
+    let (generation, position) = match header.split_once(' ') {
+        Some((generation, position)) => (generation.parse().ok()?, position),
+        None => (0, header),
+    };
+    let (index, total) = position.split_once('/')?;
+    let index: usize = index.parse().ok()?;
+    let total: usize = total.parse().ok()?;
+    if index == 0 || index > total {
+        return None;
+    }
+    Some(Chunk { generation, index, total, body })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::shard::{generation, is_chunk, join, split, ShardError, CHUNK_SIZE};
+
+    fn refs(contents: &[String]) -> Vec<&str> {
+        contents.iter().map(|content| content.as_str()).collect()
+    }
+
+    #[test]
+    fn round_trips_across_chunk_boundary() {
+        for size in [0, 1, CHUNK_SIZE - 1, CHUNK_SIZE, CHUNK_SIZE + 1, CHUNK_SIZE * 3] {
+            let payload: String = "가나다".chars().cycle().take(size).collect();
+            let chunks = split(&payload, 7);
+            assert_eq!(chunks.len(), size.div_ceil(CHUNK_SIZE).max(1));
+            assert!(chunks.iter().all(|chunk| is_chunk(chunk) && generation(chunk) == Some(7)));
+            assert!(chunks.iter().all(|chunk| chunk.chars().count() <= 2000));
+            assert_eq!(join(&refs(&chunks)).unwrap(), payload);
+        }
+    }
+
+    #[test]
+    fn joins_out_of_order_chunks() {
+        let payload = "a".repeat(CHUNK_SIZE * 2 + 10);
+        let mut chunks = split(&payload, 1);
+        chunks.reverse();
+        assert_eq!(join(&refs(&chunks)).unwrap(), payload);
+    }
+
+    #[test]
+    fn reports_missing_chunk() {
+        let mut chunks = split(&"a".repeat(CHUNK_SIZE * 3), 1);
+        chunks.remove(1);
+        assert_eq!(join(&refs(&chunks)).unwrap_err(), ShardError::Missing { generation: 1, index: 2, total: 3 });
+        assert_eq!(join(&[]).unwrap_err(), ShardError::Empty);
+    }
+
+    #[test]
+    fn rejects_mismatched_total() {
+        let contents = ["#db 1 1/2\nab", "#db 1 2/3\ncd"];
+        assert_eq!(join(&contents).unwrap_err(), ShardError::TotalMismatch { generation: 1 });
+    }
+
+    #[test]
+    fn rejects_conflicting_duplicate() {
+        assert_eq!(join(&["#db 1 1/1\nab", "#db 1 1/1\nab"]).unwrap(), "ab");
+        assert_eq!(join(&["#db 1 1/1\nab", "#db 1 1/1\ncd"]).unwrap_err(), ShardError::Duplicated { generation: 1, index: 1 });
+    }
+
+    #[test]
+    fn reads_newest_complete_generation() {
+        let mut contents = split(&"old".repeat(CHUNK_SIZE), 1);
+        // 쓰다가 실패해서 2세대는 첫 조각만 남음
+        contents.push(split(&"new".repeat(CHUNK_SIZE), 2).remove(0));
+        assert_eq!(join(&refs(&contents)).unwrap(), "old".repeat(CHUNK_SIZE));
+
+        contents.extend(split("newest", 3));
+        assert_eq!(join(&refs(&contents)).unwrap(), "newest");
+    }
+
+    #[test]
+    fn reads_chunks_without_generation() {
+        assert_eq!(join(&["#db 2/2\ncd", "#db 1/2\nab"]).unwrap(), "abcd");
+        assert_eq!(generation("#db 1/2\nab"), Some(0));
+    }
+}
@@ -1,21 +1,22 @@
+use std::sync::Arc;
 use serenity::Result;
 
-use crate::database::database::{Database, DatabaseTrait};
+use crate::database::database::DatabaseTrait;
 use crate::entity::team::Team;
 use crate::global::discord::{Guild};
 
 pub struct TeamRepo {
-    guild: Guild
+    guild: Guild,
+    database: Arc<dyn DatabaseTrait>,
 }
 
 impl TeamRepo {
-    pub fn new(guild: Guild) -> Self {
-        TeamRepo { guild }
+    pub fn new(guild: Guild, database: Arc<dyn DatabaseTrait>) -> Self {
+        TeamRepo { guild, database }
     }
 
     pub async fn get_teams(&self) -> Result<Vec<Team>> {
-        let entity = Database.get_entity(&self.guild).await?;
-        let teams = entity.teams.iter().cloned().collect();
-        Ok(teams)
+        let entity = self.database.get_entity(&self.guild).await?;
+        Ok(entity.teams)
     }
-}
\ No newline at end of file
+}
@@ -1,40 +1,478 @@
-use crate::entity::todo::Todo;
-use serenity::Result;
-use crate::database::database::{Database, DatabaseTrait};
+use std::sync::Arc;
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serenity::all::{ChannelId, ChannelType, CreateThread, EditThread};
+use crate::entity::deadline::Deadline;
+use crate::entity::team::Team;
+use crate::entity::audit::UserId;
+use crate::entity::todo::{Recurrence, Subtask, Todo, TodoContent, TodoId, TodoPriority, TodoStatus};
+use serenity::{Error, Result};
+use crate::database::meta_data_repo::MetaDataRepo;
+use crate::database::database::DatabaseTrait;
+use crate::database::transaction::update_entity;
 use crate::global::discord::{Guild};
 
+// 투두 수정 내용, None인 값은 그대로 둡니다
+#[derive(Default)]
+pub struct TodoEdit {
+    pub content: Option<String>,
+    pub deadline: Option<Deadline>,
+    pub priority: Option<TodoPriority>,
+    pub labels: Option<Vec<String>>,
+    // Some(None)이면 반복 해제
+    pub recurrence: Option<Option<Recurrence>>,
+}
+
+impl TodoEdit {
+    pub fn is_empty(&self) -> bool {
+        self.content.is_none() && self.deadline.is_none() && self.priority.is_none() && self.labels.is_none() && self.recurrence.is_none()
+    }
+}
+
+// 디스코드 채널 이름 최대 길이
+const MAX_THREAD_NAME: usize = 100;
+
+// 투두 목록 조건, None/false인 조건은 거르지 않습니다
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct TodoQuery {
+    pub team: Option<String>,
+    // 소문자
+    pub label: Option<String>,
+    pub min_priority: Option<TodoPriority>,
+    pub assignee: Option<UserId>,
+    // 내용에 포함된 글자, 소문자
+    pub search: Option<String>,
+    // guild 시간대 기준 마감 날짜 범위, 양 끝 포함
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    // 마감이 지났는데 완료하지 않은 투두만
+    pub overdue: bool,
+    // 이번주(월 ~ 일) 마감인 투두만
+    pub this_week: bool,
+    pub sort: TodoSort,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TodoSort {
+    #[default]
+    Deadline,
+    // 높은 중요도부터, 같으면 마감기한 순
+    Priority,
+    // 추가한 순
+    Created,
+}
+
+impl TodoSort {
+    // 명령어 선택지 값
+    pub fn parse(value: &str) -> Option<TodoSort> {
+        match value {
+            "deadline" => Some(TodoSort::Deadline),
+            "priority" => Some(TodoSort::Priority),
+            "created" => Some(TodoSort::Created),
+            _ => None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TodoSort::Deadline => "deadline",
+            TodoSort::Priority => "priority",
+            TodoSort::Created => "created",
+        }
+    }
+}
+
+impl TodoQuery {
+    // now는 guild 시간대의 지금, 날짜 조건은 이 시간대로 비교합니다
+    pub fn matches(&self, todo: &Todo, now: DateTime<Tz>) -> bool {
+        let content = &todo.todo;
+        let deadline = content.deadline.at.with_timezone(&now.timezone()).date_naive();
+        let today = now.date_naive();
+        let monday = today - Days::new(today.weekday().num_days_from_monday() as u64);
+
+        self.team.as_ref().is_none_or(|team| todo.team.name == *team)
+            && self.label.as_ref().is_none_or(|label| content.labels.contains(label))
+            && self.min_priority.is_none_or(|priority| content.priority >= priority)
+            && self.assignee.is_none_or(|user_id| content.assignees.contains(&user_id))
+            && self.search.as_ref().is_none_or(|search| content.content.to_lowercase().contains(search))
+            && self.from.is_none_or(|from| deadline >= from)
+            && self.to.is_none_or(|to| deadline <= to)
+            && (!self.overdue || (content.status != TodoStatus::Done && content.deadline.is_over(now.with_timezone(&Utc))))
+            && (!self.this_week || (monday <= deadline && deadline < monday + Days::new(7)))
+    }
+
+    pub fn sort(&self, todos: &mut [Todo]) {
+        match self.sort {
+            TodoSort::Deadline => todos.sort_by_key(|todo| todo.todo.deadline),
+            TodoSort::Priority => todos.sort_by_key(|todo| (std::cmp::Reverse(todo.todo.priority), todo.todo.deadline)),
+            TodoSort::Created => todos.sort_by_key(|todo| todo.id),
+        }
+    }
+
+    // 페이지 버튼 custom id에 넣는 값, 기본값이 아닌 조건만 `key=value;`로 이어붙입니다
+    pub fn state(&self) -> String {
+        let date = |date: NaiveDate| date.format("%Y%m%d").to_string();
+        let entries = [
+            ("t", self.team.clone()),
+            ("l", self.label.clone()),
+            ("p", self.min_priority.map(|priority| priority.name().to_string())),
+            ("a", self.assignee.map(|user_id| user_id.to_string())),
+            ("q", self.search.clone()),
+            ("f", self.from.map(date)),
+            ("u", self.to.map(date)),
+            ("o", self.overdue.then(String::new)),
+            ("w", self.this_week.then(String::new)),
+            ("s", (self.sort != TodoSort::Deadline).then(|| self.sort.name().to_string())),
+        ];
+        entries.into_iter()
+            .filter_map(|(key, value)| value.map(|value| format!("{key}={};", value.replace('%', "%25").replace(';', "%3B"))))
+            .collect()
+    }
+
+    pub fn parse(state: &str) -> Option<TodoQuery> {
+        let date = |value: &str| NaiveDate::parse_from_str(value, "%Y%m%d").ok();
+        let mut query = TodoQuery::default();
+        for entry in state.split(';').filter(|entry| !entry.is_empty()) {
+            let (key, value) = entry.split_once('=')?;
+            let value = value.replace("%3B", ";").replace("%25", "%");
+            match key {
+                "t" => query.team = Some(value),
+                "l" => query.label = Some(value),
+                "p" => query.min_priority = Some(TodoPriority::parse(&value)?),
+                "a" => query.assignee = Some(value.parse().ok()?),
+                "q" => query.search = Some(value),
+                "f" => query.from = Some(date(&value)?),
+                "u" => query.to = Some(date(&value)?),
+                "o" => query.overdue = true,
+                "w" => query.this_week = true,
+                "s" => query.sort = TodoSort::parse(&value)?,
+                _ => return None
+            }
+        }
+        Some(query)
+    }
+}
+
 pub struct TodoRepo {
-    pub guild: Guild
+    pub guild: Guild,
+    database: Arc<dyn DatabaseTrait>,
 }
 
 impl TodoRepo {
-    pub fn new(guild: Guild) -> Self {
-        TodoRepo { guild }
+    pub fn new(guild: Guild, database: Arc<dyn DatabaseTrait>) -> Self {
+        TodoRepo { guild, database }
     }
 
     pub async fn get_todos(&self) -> Result<Vec<Todo>> {
-        let entity = Database.get_entity(&self.guild).await?;
-        let todos = entity.todos.iter().cloned().collect();
+        let entity = self.database.get_entity(&self.guild).await?;
+        Ok(entity.todos)
+    }
+
+    pub async fn get_todos_by_team(&self, team_name: &str) -> Result<Vec<Todo>> {
+        let todos = self.get_todos().await?;
+        let todos = todos.iter().filter(|todo| todo.team.name == team_name).cloned().collect();
         Ok(todos)
     }
 
-    pub async fn get_todos_by_team(&self, team_name: &String) -> Result<Vec<Todo>> {
+    // 담당자로 지정된 투두, 완료한 투두는 제외
+    pub async fn get_open_todos_by_assignee(&self, user_id: UserId) -> Result<Vec<Todo>> {
         let todos = self.get_todos().await?;
-        let todos = todos.iter().filter(|todo| todo.team.name == team_name.clone()).cloned().collect();
+        let todos = todos.into_iter()
+            .filter(|todo| todo.todo.status != TodoStatus::Done && todo.todo.assignees.contains(&user_id))
+            .collect();
         Ok(todos)
     }
 
-    pub async fn create_todo(&self, todo: &Todo) -> Result<()> {
-        let mut entity = Database.get_entity(&self.guild).await?;
-        entity.todos.push(todo.clone());
-        Database.edit_entity(&self.guild, &entity).await?;
-        Ok(())
+    // query 조건에 맞는 투두를 query 순서대로
+    pub async fn query(&self, query: &TodoQuery, now: DateTime<Tz>) -> Result<Vec<Todo>> {
+        let mut todos: Vec<Todo> = self.get_todos().await?
+            .into_iter()
+            .filter(|todo| query.matches(todo, now))
+            .collect();
+        query.sort(&mut todos);
+        Ok(todos)
     }
 
+    pub async fn get_todo(&self, id: TodoId) -> Result<Todo> {
+        let todos = self.get_todos().await?;
+        todos.into_iter()
+            .find(|todo| todo.id == id)
+            .ok_or_else(|| Error::Other("투두를 찾을 수 없습니다"))
+    }
+
+    // 새 id를 붙여서 추가합니다
+    pub async fn create_todo(&self, team: &Team, content: &TodoContent) -> Result<Todo> {
+        let entity = update_entity(self.database.as_ref(), &self.guild, |entity| {
+            let id = entity.next_todo_id;
+            entity.next_todo_id += 1;
+            entity.todos.push(Todo { id, team: team.clone(), todo: content.clone() });
+            Ok(())
+        }).await?;
+        entity.todos.last().cloned().ok_or_else(|| Error::Other("투두를 찾을 수 없습니다"))
+    }
+
+    pub async fn edit_todo(&self, id: TodoId, edit: &TodoEdit) -> Result<Todo> {
+        let entity = update_entity(self.database.as_ref(), &self.guild, |entity| {
+            let todo = entity.todos.iter_mut()
+                .find(|todo| todo.id == id)
+                .ok_or_else(|| Error::Other("투두를 찾을 수 없습니다"))?;
+            if let Some(content) = &edit.content {
+                todo.todo.content = content.clone();
+            }
+            if let Some(deadline) = edit.deadline {
+                todo.todo.deadline = deadline;
+            }
+            if let Some(priority) = edit.priority {
+                todo.todo.priority = priority;
+            }
+            if let Some(labels) = &edit.labels {
+                todo.todo.labels = labels.clone();
+            }
+            if let Some(recurrence) = edit.recurrence {
+                todo.todo.recurrence = recurrence;
+            }
+            Ok(())
+        }).await?;
+        entity.todos.into_iter().find(|todo| todo.id == id).ok_or_else(|| Error::Other("투두를 찾을 수 없습니다"))
+    }
+
+    // 완료하면 스레드를 보관하고, 다시 열면 스레드도 다시 엽니다
+    pub async fn edit_status(&self, id: TodoId, status: TodoStatus) -> Result<Todo> {
+        self.update_status(id, |_| status).await
+    }
+
+    // 할 일 -> 진행 중 -> 완료 -> 할 일, 저장된 상태를 기준으로 lock 안에서 다음 상태를 정합니다
+    pub async fn cycle_status(&self, id: TodoId) -> Result<Todo> {
+        self.update_status(id, TodoStatus::next).await
+    }
+
+    async fn update_status<F>(&self, id: TodoId, status: F) -> Result<Todo>
+    where
+        F: Fn(TodoStatus) -> TodoStatus + Send + Sync,
+    {
+        let entity = update_entity(self.database.as_ref(), &self.guild, |entity| {
+            let todo = entity.todos.iter_mut()
+                .find(|todo| todo.id == id)
+                .ok_or_else(|| Error::Other("투두를 찾을 수 없습니다"))?;
+            todo.todo.status = status(todo.todo.status);
+            Ok(())
+        }).await?;
+        let todo = entity.todos.into_iter().find(|todo| todo.id == id).ok_or_else(|| Error::Other("투두를 찾을 수 없습니다"))?;
+        self.archive_thread(&todo, todo.todo.status == TodoStatus::Done).await;
+        Ok(todo)
+    }
+
+    // channel_id 채널에 투두 논의용 스레드를 만들고 투두에 연결합니다
+    pub async fn create_thread(&self, id: TodoId, channel_id: ChannelId) -> Result<Todo> {
+        let todo = self.get_todo(id).await?;
+        let name: String = todo.label().chars().take(MAX_THREAD_NAME).collect();
+        let builder = CreateThread::new(name).kind(ChannelType::PublicThread);
+        let thread = self.guild.api.create_thread(channel_id, builder).await?;
+
+        let entity = update_entity(self.database.as_ref(), &self.guild, |entity| {
+            let todo = entity.todos.iter_mut()
+                .find(|todo| todo.id == id)
+                .ok_or_else(|| Error::Other("투두를 찾을 수 없습니다"))?;
+            todo.todo.thread_id = Some(thread.id.get());
+            Ok(())
+        }).await?;
+        entity.todos.into_iter().find(|todo| todo.id == id).ok_or_else(|| Error::Other("투두를 찾을 수 없습니다"))
+    }
+
+    // 스레드 보관은 투두 변경과 별개라 실패해도 변경은 그대로 둡니다
+    async fn archive_thread(&self, todo: &Todo, archived: bool) {
+        let Some(thread_id) = todo.todo.thread_id else { return };
+        let builder = EditThread::new().archived(archived);
+        if let Err(why) = self.guild.api.edit_thread(ChannelId::new(thread_id), builder).await {
+            println!("스레드 보관 실패 {} - {}", thread_id, why);
+        }
+    }
+
+    pub async fn edit_assignees(&self, id: TodoId, assignees: Vec<UserId>) -> Result<Todo> {
+        let entity = update_entity(self.database.as_ref(), &self.guild, |entity| {
+            let todo = entity.todos.iter_mut()
+                .find(|todo| todo.id == id)
+                .ok_or_else(|| Error::Other("투두를 찾을 수 없습니다"))?;
+            todo.todo.assignees = assignees.clone();
+            Ok(())
+        }).await?;
+        entity.todos.into_iter().find(|todo| todo.id == id).ok_or_else(|| Error::Other("투두를 찾을 수 없습니다"))
+    }
+
+    pub async fn add_subtask(&self, id: TodoId, content: &str) -> Result<Todo> {
+        self.edit_checklist(id, |checklist| {
+            checklist.push(Subtask { content: content.to_string(), done: false });
+            Ok(())
+        }).await
+    }
+
+    // index는 0부터
+    pub async fn remove_subtask(&self, id: TodoId, index: usize) -> Result<Todo> {
+        self.edit_checklist(id, |checklist| {
+            if index >= checklist.len() {
+                Err(Error::Other("하위 작업을 찾을 수 없습니다"))?
+            }
+            checklist.remove(index);
+            Ok(())
+        }).await
+    }
+
+    pub async fn toggle_subtask(&self, id: TodoId, index: usize) -> Result<Todo> {
+        self.edit_checklist(id, |checklist| {
+            let subtask = checklist.get_mut(index).ok_or_else(|| Error::Other("하위 작업을 찾을 수 없습니다"))?;
+            subtask.done = !subtask.done;
+            Ok(())
+        }).await
+    }
+
+    async fn edit_checklist<F>(&self, id: TodoId, edit: F) -> Result<Todo>
+    where
+        F: Fn(&mut Vec<Subtask>) -> Result<()> + Send + Sync,
+    {
+        let entity = update_entity(self.database.as_ref(), &self.guild, |entity| {
+            let todo = entity.todos.iter_mut()
+                .find(|todo| todo.id == id)
+                .ok_or_else(|| Error::Other("투두를 찾을 수 없습니다"))?;
+            edit(&mut todo.todo.checklist)
+        }).await?;
+        entity.todos.into_iter().find(|todo| todo.id == id).ok_or_else(|| Error::Other("투두를 찾을 수 없습니다"))
+    }
+
+    // 삭제된 투두를 돌려줍니다
+    pub async fn delete_todo(&self, id: TodoId) -> Result<Todo> {
+        let todo = self.get_todo(id).await?;
+        update_entity(self.database.as_ref(), &self.guild, |entity| {
+            let before = entity.todos.len();
+            entity.todos.retain(|todo| todo.id != id);
+            if entity.todos.len() == before {
+                Err(Error::Other("투두를 찾을 수 없습니다"))?
+            }
+            Ok(())
+        }).await?;
+        self.archive_thread(&todo, true).await;
+        Ok(todo)
+    }
+
+    // 반복 투두는 다시 입력하지 않아도 되도록 남겨둡니다
     pub async fn reset_todo(&self) -> Result<()> {
-        let mut entity = Database.get_entity(&self.guild).await?;
-        entity.todos = vec![];
-        Database.edit_entity(&self.guild, &entity).await?;
+        let deleted: Vec<Todo> = self.get_todos().await?.into_iter().filter(|todo| todo.todo.recurrence.is_none()).collect();
+        update_entity(self.database.as_ref(), &self.guild, |entity| {
+            entity.todos.retain(|todo| todo.todo.recurrence.is_some());
+            Ok(())
+        }).await?;
+        for todo in &deleted {
+            self.archive_thread(todo, true).await;
+        }
         Ok(())
     }
-}
\ No newline at end of file
+
+    // 완료됐거나 마감이 지난 반복 투두를 다음 마감기한으로 새로 만듭니다
+    // 이전 투두는 기록으로 남기고 반복만 해제, 새로 만든 투두 개수를 돌려줍니다
+    pub async fn regenerate_recurring(&self, now: DateTime<Utc>) -> Result<usize> {
+        let is_due = |todo: &Todo| todo.todo.recurrence.is_some() && (todo.todo.status == TodoStatus::Done || todo.todo.deadline.is_over(now));
+        let count = self.get_todos().await?.iter().filter(|todo| is_due(todo)).count();
+        if count == 0 {
+            return Ok(0);
+        }
+
+        // 서머타임이 있는 시간대에서도 같은 시각에 반복되도록 guild 시간대 기준으로 계산
+        let timezone = MetaDataRepo::new(self.guild.clone(), self.database.clone()).get_timezone().await?;
+        update_entity(self.database.as_ref(), &self.guild, |entity| {
+            let mut next_todos = vec![];
+            for todo in entity.todos.iter_mut().filter(|todo| is_due(todo)) {
+                let Some(recurrence) = todo.todo.recurrence.take() else { continue };
+                let recurrence = recurrence.anchored(todo.todo.deadline, timezone);
+                let mut deadline = recurrence.next_deadline(todo.todo.deadline, timezone);
+                while deadline.is_over(now) {
+                    deadline = recurrence.next_deadline(deadline, timezone);
+                }
+                let mut content = todo.todo.clone();
+                content.deadline = deadline;
+                content.status = TodoStatus::Todo;
+                content.recurrence = Some(recurrence);
+                content.checklist.iter_mut().for_each(|subtask| subtask.done = false);
+                content.thread_id = None;
+                next_todos.push((todo.team.clone(), content));
+            }
+            for (team, content) in next_todos {
+                let id = entity.next_todo_id;
+                entity.next_todo_id += 1;
+                entity.todos.push(Todo { id, team, todo: content });
+            }
+            Ok(())
+        }).await?;
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone};
+    use chrono_tz::Asia::Seoul;
+
+    use crate::database::todo_repo::{TodoQuery, TodoSort};
+    use crate::entity::deadline::Deadline;
+    use crate::entity::team::Team;
+    use crate::entity::todo::{Todo, TodoContent, TodoPriority, TodoStatus};
+
+    fn todo(id: u64, team: &str, content: &str, day: u32, priority: TodoPriority, status: TodoStatus) -> Todo {
+        let deadline = Deadline::date(NaiveDate::from_ymd_opt(2025, 3, day).unwrap(), Seoul);
+        let content = TodoContent { status, assignees: vec![100], priority, labels: vec![String::from("bug")], ..TodoContent::new(content.to_string(), deadline) };
+        Todo { id, team: Team { name: team.to_string() }, todo: content }
+    }
+
+    fn todos() -> Vec<Todo> {
+        vec![
+            todo(1, "iOS", "로그인 화면", 12, TodoPriority::Normal, TodoStatus::Todo),
+            todo(2, "Web", "Login API", 3, TodoPriority::Urgent, TodoStatus::InProgress),
+            todo(3, "iOS", "설정", 7, TodoPriority::High, TodoStatus::Done),
+        ]
+    }
+
+    fn ids(query: &TodoQuery) -> Vec<u64> {
+        // 2025-03-05 수요일 09:00
+        let now = Seoul.with_ymd_and_hms(2025, 3, 5, 9, 0, 0).unwrap();
+        let mut todos: Vec<Todo> = todos().into_iter().filter(|todo| query.matches(todo, now)).collect();
+        query.sort(&mut todos);
+        todos.iter().map(|todo| todo.id).collect()
+    }
+
+    #[test]
+    fn filters_todos() {
+        assert_eq!(ids(&TodoQuery::default()), vec![2, 3, 1]);
+        assert_eq!(ids(&TodoQuery { team: Some(String::from("iOS")), ..Default::default() }), vec![3, 1]);
+        assert_eq!(ids(&TodoQuery { search: Some(String::from("login")), ..Default::default() }), vec![2]);
+        assert_eq!(ids(&TodoQuery { overdue: true, ..Default::default() }), vec![2]);
+        assert_eq!(ids(&TodoQuery { this_week: true, ..Default::default() }), vec![2, 3]);
+        assert_eq!(ids(&TodoQuery { assignee: Some(200), ..Default::default() }), Vec::<u64>::new());
+        let from = NaiveDate::from_ymd_opt(2025, 3, 7);
+        assert_eq!(ids(&TodoQuery { from, to: NaiveDate::from_ymd_opt(2025, 3, 12), ..Default::default() }), vec![3, 1]);
+    }
+
+    #[test]
+    fn sorts_todos() {
+        assert_eq!(ids(&TodoQuery { sort: TodoSort::Priority, ..Default::default() }), vec![2, 3, 1]);
+        assert_eq!(ids(&TodoQuery { sort: TodoSort::Created, ..Default::default() }), vec![1, 2, 3]);
+        assert_eq!(ids(&TodoQuery { min_priority: Some(TodoPriority::High), sort: TodoSort::Created, ..Default::default() }), vec![2, 3]);
+    }
+
+    #[test]
+    fn round_trips_state() {
+        let query = TodoQuery {
+            team: Some(String::from("iOS")),
+            label: Some(String::from("bug")),
+            min_priority: Some(TodoPriority::High),
+            assignee: Some(100),
+            search: Some(String::from("a;b=c%")),
+            from: NaiveDate::from_ymd_opt(2025, 3, 1),
+            to: NaiveDate::from_ymd_opt(2025, 3, 31),
+            overdue: true,
+            this_week: true,
+            sort: TodoSort::Priority,
+        };
+        assert_eq!(TodoQuery::parse(&query.state()), Some(query));
+        assert_eq!(TodoQuery::default().state(), "");
+        assert_eq!(TodoQuery::parse(""), Some(TodoQuery::default()));
+        assert_eq!(TodoQuery::parse("x=1;"), None);
+    }
+}
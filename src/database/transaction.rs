@@ -0,0 +1,369 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, LazyLock, Mutex};
+
+use serenity::all::GuildId;
+use serenity::{Error, Result};
+use tokio::sync::OwnedMutexGuard;
+
+use crate::database::audit_repo::{mirror, record};
+use crate::database::database::DatabaseTrait;
+use crate::database::undo;
+use crate::database::undo::UndoEntry;
+use crate::entity::entity::Entity;
+use crate::global::discord::Guild;
+use crate::util::crypto::is_key_error;
+
+pub const CONFLICT_MESSAGE: &str = "다른 변경사항과 충돌했습니다. 잠시 후 다시 시도해주세요";
+const MAX_RETRY: usize = 3;
+
+static LOCKS: LazyLock<Mutex<HashMap<GuildId, Arc<tokio::sync::Mutex<()>>>>> = LazyLock::new(Default::default);
+
+tokio::task_local! {
+    // 지금 task가 with_lock으로 들고 있는 guild lock
+    static HELD_LOCK: GuildId;
+}
+
+// guild 단위로 쓰기를 직렬화하기 위한 lock
+async fn lock(guild_id: GuildId) -> OwnedMutexGuard<()> {
+    let lock = LOCKS.lock()
+        .unwrap()
+        .entry(guild_id)
+        .or_default()
+        .clone();
+    lock.lock_owned().await
+}
+
+// guild lock을 잡은 채로 future를 실행합니다
+pub async fn with_lock<F: Future>(guild_id: GuildId, future: F) -> F::Output {
+    let _guard = lock(guild_id).await;
+    HELD_LOCK.scope(guild_id, future).await
+}
+
+// with_lock 안에서 다시 부르면 이미 잡은 lock을 기다리지 않도록 None
+pub async fn lock_unless_held(guild_id: GuildId) -> Option<OwnedMutexGuard<()>> {
+    if HELD_LOCK.try_with(|held| *held == guild_id).unwrap_or(false) {
+        return None;
+    }
+    Some(lock(guild_id).await)
+}
+
+// 저장된 revision과 쓰려는 entity의 revision이 같을 때만 다음 revision을 돌려줍니다
+pub fn next_revision(current: &Entity, entity: &Entity) -> Result<Entity> {
+    if current.revision != entity.revision {
+        Err(Error::Other(CONFLICT_MESSAGE))?
+    }
+    let mut entity = entity.clone();
+    entity.revision = current.revision + 1;
+    Ok(entity)
+}
+
+pub fn is_conflict(error: &Error) -> bool {
+    matches!(error, Error::Other(message) if *message == CONFLICT_MESSAGE)
+}
+
+// get_entity -> mutate -> edit_entity를 guild lock 안에서 실행하고, 충돌하면 다시 시도합니다
+pub async fn update_entity<F>(database: &dyn DatabaseTrait, guild: &Guild, mutate: F) -> Result<Entity>
+where
+    F: Fn(&mut Entity) -> Result<()> + Send + Sync,
+{
+    with_lock(guild.guild_id, update_locked(database, guild, mutate)).await
+}
+
+async fn update_locked<F>(database: &dyn DatabaseTrait, guild: &Guild, mutate: F) -> Result<Entity>
+where
+    F: Fn(&mut Entity) -> Result<()> + Send + Sync,
+{
+    let mut retry = 0;
+    loop {
+        let mut entity = database.get_entity(guild).await?;
+        let before = entity.clone();
+        mutate(&mut entity)?;
+        record(guild, &before, &mut entity);
+        match database.edit_entity(guild, &entity).await {
+            Ok(()) => {
+                entity.revision += 1;
+                push_undo(guild, before, &entity);
+                mirror(guild, &entity).await;
+                return Ok(entity);
+            }
+            Err(why) if is_conflict(&why) && retry < MAX_RETRY => {
+                println!("revision 충돌, 다시 시도합니다 ({})", retry + 1);
+                retry += 1;
+            }
+            Err(why) => return Err(why),
+        }
+    }
+}
+
+// force-import, reset처럼 전체를 덮어쓰는 경우, 현재 revision과 변경 기록은 이어받습니다
+pub async fn replace_entity(database: &dyn DatabaseTrait, guild: &Guild, entity: Entity) -> Result<Entity> {
+    with_lock(guild.guild_id, replace_locked(database, guild, entity)).await
+}
+
+async fn replace_locked(database: &dyn DatabaseTrait, guild: &Guild, entity: Entity) -> Result<Entity> {
+    let mut retry = 0;
+    loop {
+        // 기존 데이터가 깨져있으면 초기화 후 덮어씀, 암호화 키가 틀린 경우는 제외
+        let before = match database.get_entity(guild).await {
+            Ok(v) => v,
+            Err(why) if is_key_error(&why) => Err(why)?,
+            Err(_) => {
+                database.init_entity(guild).await?;
+                Entity::empty()
+            }
+        };
+        let mut entity = entity.clone();
+        entity.revision = before.revision;
+        entity.audit_log = before.audit_log.clone();
+        record(guild, &before, &mut entity);
+        match database.edit_entity(guild, &entity).await {
+            Ok(()) => {
+                entity.revision += 1;
+                push_undo(guild, before, &entity);
+                mirror(guild, &entity).await;
+                return Ok(entity);
+            }
+            Err(why) if is_conflict(&why) && retry < MAX_RETRY => {
+                println!("revision 충돌, 다시 시도합니다 ({})", retry + 1);
+                retry += 1;
+            }
+            Err(why) => return Err(why),
+        }
+    }
+}
+
+// 마지막 변경을 되돌립니다, 변경한 사용자 본인 또는 관리자만 가능
+pub async fn undo_entity(database: &dyn DatabaseTrait, guild: &Guild, is_admin: bool) -> Result<Entity> {
+    with_lock(guild.guild_id, undo_locked(database, guild, is_admin)).await
+}
+
+async fn undo_locked(database: &dyn DatabaseTrait, guild: &Guild, is_admin: bool) -> Result<Entity> {
+    let entry = undo::last(guild.guild_id)
+        .ok_or_else(|| Error::Other("되돌릴 변경이 없습니다"))?;
+    let user_id = guild.actor.as_ref().map(|actor| actor.user_id.get());
+    if !is_admin && (entry.user_id.is_none() || entry.user_id != user_id) {
+        Err(Error::Other("본인이 한 변경 또는 관리자만 되돌릴 수 있습니다"))?
+    }
+
+    let current = database.get_entity(guild).await?;
+    if current.revision != entry.revision {
+        Err(Error::Other("이후에 다른 곳에서 변경되어 되돌릴 수 없습니다"))?
+    }
+
+    // 되돌린 기록도 남기기 위해 변경 기록은 현재 것을 이어받음
+    let mut entity = entry.before;
+    entity.revision = current.revision;
+    entity.audit_log = current.audit_log.clone();
+    record(guild, &current, &mut entity);
+    database.edit_entity(guild, &entity).await?;
+    entity.revision += 1;
+    undo::pop(guild.guild_id, entity.revision);
+    mirror(guild, &entity).await;
+    Ok(entity)
+}
+
+fn push_undo(guild: &Guild, before: Entity, entity: &Entity) {
+    let entry = UndoEntry {
+        before,
+        user_id: guild.actor.as_ref().map(|actor| actor.user_id.get()),
+        revision: entity.revision,
+    };
+    undo::push(guild.guild_id, entry);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use serenity::all::{GuildId, UserId};
+    use serenity::async_trait;
+    use serenity::Result;
+
+    use crate::database::database::{Database, DatabaseTrait};
+    use crate::database::transaction::{is_conflict, next_revision, replace_entity, undo_entity, update_entity, MAX_RETRY};
+    use crate::entity::entity::Entity;
+    use crate::entity::team::Team;
+    use crate::global::discord::{Actor, Guild};
+    use crate::global::fake_discord::{FakeDiscord, USER_ID};
+
+    // 되돌리기 기록은 guild 별로 전역에 보관되므로 테스트마다 다른 guild를 사용
+    fn guild(api: &Arc<FakeDiscord>, guild_id: u64, user_id: UserId) -> Guild {
+        let mut guild = Guild::new(api.clone(), GuildId::new(guild_id));
+        guild.actor = Some(Actor { user_id, command: String::from("test") });
+        guild
+    }
+
+    async fn add_team(database: &Database, guild: &Guild, name: &str) {
+        update_entity(database, guild, |entity| {
+            entity.teams.push(Team { name: name.to_string() });
+            Ok(())
+        }).await.unwrap();
+    }
+
+    // 읽은 뒤 쓰기 전에 다른 곳에서 conflicts번 먼저 저장해 revision을 올리는 저장소
+    struct ConflictingDatabase {
+        database: Database,
+        conflicts: AtomicUsize,
+    }
+
+    impl ConflictingDatabase {
+        fn new(conflicts: usize) -> Self {
+            ConflictingDatabase { database: Database::default(), conflicts: AtomicUsize::new(conflicts) }
+        }
+    }
+
+    #[async_trait]
+    impl DatabaseTrait for ConflictingDatabase {
+        async fn get_entity(&self, guild: &Guild) -> Result<Entity> {
+            self.database.get_entity(guild).await
+        }
+
+        async fn edit_entity(&self, guild: &Guild, entity: &Entity) -> Result<()> {
+            let remaining = self.conflicts.load(Ordering::SeqCst);
+            if remaining > 0 {
+                self.conflicts.store(remaining - 1, Ordering::SeqCst);
+                let mut outside = self.database.get_entity(guild).await?;
+                outside.teams.push(Team { name: format!("outside-{remaining}") });
+                self.database.edit_entity(guild, &outside).await?;
+            }
+            self.database.edit_entity(guild, entity).await
+        }
+
+        async fn init_entity(&self, guild: &Guild) -> Result<()> {
+            self.database.init_entity(guild).await
+        }
+    }
+
+    async fn team_names(database: &dyn DatabaseTrait, guild: &Guild) -> Vec<String> {
+        let entity = database.get_entity(guild).await.unwrap();
+        entity.teams.into_iter().map(|team| team.name).collect()
+    }
+
+    #[tokio::test]
+    async fn undoes_last_change() {
+        let api = Arc::new(FakeDiscord::new());
+        let guild = guild(&api, 201, USER_ID);
+        let database = Database::default();
+        let teams = team_names(&database, &guild).await;
+        add_team(&database, &guild, "QA").await;
+
+        let entity = undo_entity(&database, &guild, false).await.unwrap();
+        assert_eq!(entity.revision, 2);
+        assert_eq!(team_names(&database, &guild).await, teams);
+        assert!(undo_entity(&database, &guild, false).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn undoes_several_changes_in_a_row() {
+        let api = Arc::new(FakeDiscord::new());
+        let guild = guild(&api, 202, USER_ID);
+        let database = Database::default();
+        let teams = team_names(&database, &guild).await;
+        for name in ["QA", "Data", "Infra"] {
+            add_team(&database, &guild, name).await;
+        }
+
+        undo_entity(&database, &guild, false).await.unwrap();
+        assert_eq!(team_names(&database, &guild).await.last().unwrap(), "Data");
+        undo_entity(&database, &guild, false).await.unwrap();
+        let entity = undo_entity(&database, &guild, false).await.unwrap();
+        assert_eq!(entity.revision, 6);
+        assert_eq!(team_names(&database, &guild).await, teams);
+    }
+
+    #[tokio::test]
+    async fn refuses_other_users_change() {
+        let api = Arc::new(FakeDiscord::new());
+        let database = Database::default();
+        add_team(&database, &guild(&api, 203, USER_ID), "QA").await;
+
+        let other = guild(&api, 203, UserId::new(USER_ID.get() + 1));
+        assert!(undo_entity(&database, &other, false).await.is_err());
+        assert!(team_names(&database, &other).await.contains(&String::from("QA")));
+
+        // 관리자는 다른 사용자의 변경도 되돌릴 수 있음
+        undo_entity(&database, &other, true).await.unwrap();
+        assert!(!team_names(&database, &other).await.contains(&String::from("QA")));
+    }
+
+    #[tokio::test]
+    async fn refuses_after_outside_change() {
+        let api = Arc::new(FakeDiscord::new());
+        let guild = guild(&api, 204, USER_ID);
+        let database = Database::default();
+        add_team(&database, &guild, "QA").await;
+
+        // 되돌리기 기록을 남기지 않는 다른 곳에서의 변경
+        let entity = database.get_entity(&guild).await.unwrap();
+        database.edit_entity(&guild, &entity).await.unwrap();
+        assert!(undo_entity(&database, &guild, true).await.is_err());
+        assert!(team_names(&database, &guild).await.contains(&String::from("QA")));
+    }
+
+    #[test]
+    fn bumps_revision_only_when_unchanged() {
+        let mut current = Entity::empty();
+        current.revision = 3;
+        assert_eq!(next_revision(&current, &current).unwrap().revision, 4);
+
+        let mut stale = current.clone();
+        stale.revision = 2;
+        assert!(matches!(next_revision(&current, &stale), Err(why) if is_conflict(&why)));
+    }
+
+    #[tokio::test]
+    async fn retries_update_on_conflict() {
+        let api = Arc::new(FakeDiscord::new());
+        let guild = guild(&api, 205, USER_ID);
+        let database = ConflictingDatabase::new(MAX_RETRY);
+        let attempts = AtomicUsize::new(0);
+
+        let entity = update_entity(&database, &guild, |entity| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            entity.teams.push(Team { name: String::from("QA") });
+            Ok(())
+        }).await.unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), MAX_RETRY + 1);
+        assert_eq!(entity.revision, MAX_RETRY as u64 + 1);
+
+        // 다른 곳의 변경을 덮어쓰지 않고 그 위에 한 번만 적용됨
+        let teams = team_names(&database, &guild).await;
+        assert_eq!(teams.iter().filter(|name| name.starts_with("outside-")).count(), MAX_RETRY);
+        assert_eq!(teams.iter().filter(|name| *name == "QA").count(), 1);
+        assert_eq!(teams.last().unwrap(), "QA");
+    }
+
+    #[tokio::test]
+    async fn gives_up_update_after_max_retry() {
+        let api = Arc::new(FakeDiscord::new());
+        let guild = guild(&api, 206, USER_ID);
+        let database = ConflictingDatabase::new(MAX_RETRY + 1);
+
+        let result = update_entity(&database, &guild, |entity| {
+            entity.teams.push(Team { name: String::from("QA") });
+            Ok(())
+        }).await;
+        assert!(matches!(result, Err(why) if is_conflict(&why)));
+        assert!(!team_names(&database, &guild).await.contains(&String::from("QA")));
+    }
+
+    #[tokio::test]
+    async fn retries_replace_on_conflict() {
+        let api = Arc::new(FakeDiscord::new());
+        let guild = guild(&api, 207, USER_ID);
+        let database = ConflictingDatabase::new(1);
+        let mut imported = Entity::empty();
+        imported.teams = vec![Team { name: String::from("QA") }];
+
+        let entity = replace_entity(&database, &guild, imported.clone()).await.unwrap();
+        assert_eq!(entity.revision, 2);
+        assert_eq!(team_names(&database, &guild).await, vec![String::from("QA")]);
+
+        let database = ConflictingDatabase::new(MAX_RETRY + 1);
+        let guild = self::guild(&api, 208, USER_ID);
+        let result = replace_entity(&database, &guild, imported).await;
+        assert!(matches!(result, Err(why) if is_conflict(&why)));
+    }
+}
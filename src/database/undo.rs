@@ -0,0 +1,45 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{LazyLock, Mutex};
+
+use serenity::all::GuildId;
+
+use crate::entity::audit::UserId;
+use crate::entity::entity::Entity;
+
+const MAX_UNDO: usize = 10;
+
+// 되돌리기 위해 보관하는 변경 직전 상태
+#[derive(Clone)]
+pub struct UndoEntry {
+    pub before: Entity,
+    pub user_id: Option<UserId>,
+    // 이 변경으로 저장된 revision, 이후 다른 변경이 있었는지 확인할 때 사용
+    pub revision: u64,
+}
+
+// 메모리에만 보관하므로 봇이 재시작되면 비워집니다
+static STACKS: LazyLock<Mutex<HashMap<GuildId, VecDeque<UndoEntry>>>> = LazyLock::new(Default::default);
+
+pub fn push(guild_id: GuildId, entry: UndoEntry) {
+    let mut stacks = STACKS.lock().unwrap();
+    let stack = stacks.entry(guild_id).or_default();
+    stack.push_back(entry);
+    if stack.len() > MAX_UNDO {
+        stack.pop_front();
+    }
+}
+
+pub fn last(guild_id: GuildId) -> Option<UndoEntry> {
+    STACKS.lock().unwrap().get(&guild_id).and_then(|stack| stack.back().cloned())
+}
+
+// 되돌린 뒤 저장된 revision을 다음 항목이 이어받아야 연달아 되돌릴 수 있습니다
+pub fn pop(guild_id: GuildId, revision: u64) -> Option<UndoEntry> {
+    let mut stacks = STACKS.lock().unwrap();
+    let stack = stacks.get_mut(&guild_id)?;
+    let entry = stack.pop_back();
+    if let Some(next) = stack.back_mut() {
+        next.revision = revision;
+    }
+    entry
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub type UserId = u64;
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct AuditEntry {
+    // 스케쥴러처럼 사용자가 없는 변경은 None
+    pub user_id: Option<UserId>,
+    pub command: String,
+    pub timestamp: DateTime<Utc>,
+    pub summary: String,
+}
+
+impl AuditEntry {
+    pub fn message(&self) -> String {
+        let user = match self.user_id {
+            Some(user_id) => format!("<@{}>", user_id),
+            None => "🤖".to_string()
+        };
+        format!("<t:{}:f> {} `{}` {}", self.timestamp.timestamp(), user, self.command, self.summary)
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+// 날짜만 입력한 마감기한은 그 날이 끝날 때까지
+const END_OF_DAY: NaiveTime = NaiveTime::from_hms_opt(23, 59, 59).expect("올바른 시각");
+
+// 마감 시각, 입력한 시간대의 offset을 함께 저장합니다 ex. `2024-03-02T23:59:59+09:00`
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Deadline {
+    pub at: DateTime<FixedOffset>,
+    // 시간 없이 날짜만 입력한 경우
+    pub all_day: bool,
+}
+
+impl Deadline {
+    pub fn date(date: NaiveDate, timezone: Tz) -> Self {
+        Deadline { at: local(date.and_time(END_OF_DAY), timezone), all_day: true }
+    }
+
+    pub fn date_time(date_time: NaiveDateTime, timezone: Tz) -> Self {
+        Deadline { at: local(date_time, timezone), all_day: false }
+    }
+
+    pub fn is_over(&self, now: DateTime<Utc>) -> bool {
+        self.at.with_timezone(&Utc) < now
+    }
+
+    // 디스코드 timestamp, 보는 사람의 시간대로 표시됩니다
+    pub fn timestamp(&self) -> String {
+        let style = if self.all_day { 'D' } else { 'f' };
+        format!("<t:{}:{style}>", self.at.timestamp())
+    }
+
+    // timestamp를 쓸 수 없는 곳(자동완성 등)에서 쓰는 입력한 시간대 기준 표시 ex. `03/02`, `03/02 18:00`
+    pub fn label(&self) -> String {
+        let format = if self.all_day { "%m/%d" } else { "%m/%d %H:%M" };
+        self.at.format(format).to_string()
+    }
+
+    // timezone 기준 마감 날짜
+    pub fn local_date(&self, timezone: Tz) -> NaiveDate {
+        self.at.with_timezone(&timezone).date_naive()
+    }
+
+    // timezone 기준 시각은 그대로 두고 날짜만 바꿉니다, 서머타임으로 offset이 바뀌어도 같은 시각
+    pub fn with_local_date(self, date: NaiveDate, timezone: Tz) -> Self {
+        let time = self.at.with_timezone(&timezone).time();
+        Deadline { at: local(date.and_time(time), timezone), ..self }
+    }
+}
+
+// 서머타임으로 없는 시각이면 utc 기준으로
+fn local(date_time: NaiveDateTime, timezone: Tz) -> DateTime<FixedOffset> {
+    timezone.from_local_datetime(&date_time)
+        .earliest()
+        .unwrap_or_else(|| timezone.from_utc_datetime(&date_time))
+        .fixed_offset()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone, Utc};
+    use chrono_tz::Asia::Seoul;
+
+    use crate::entity::deadline::Deadline;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, 2).unwrap()
+    }
+
+    #[test]
+    fn stores_offset() {
+        let deadline = Deadline::date(date(), Seoul);
+        assert_eq!(serenity::json::to_value(deadline).unwrap()["at"], "2024-03-02T23:59:59+09:00");
+        assert_eq!(deadline.label(), "03/02");
+    }
+
+    #[test]
+    fn renders_timestamp() {
+        let deadline = Deadline::date_time(date().and_hms_opt(18, 0, 0).unwrap(), Seoul);
+        assert_eq!(deadline.timestamp(), "<t:1709370000:f>");
+        assert_eq!(deadline.label(), "03/02 18:00");
+        assert_eq!(Deadline::date(date(), Seoul).timestamp(), "<t:1709391599:D>");
+    }
+
+    #[test]
+    fn is_over_after_end_of_day() {
+        let deadline = Deadline::date(date(), Seoul);
+        assert!(!deadline.is_over(Utc.with_ymd_and_hms(2024, 3, 2, 14, 59, 0).unwrap()));
+        assert!(deadline.is_over(Utc.with_ymd_and_hms(2024, 3, 2, 15, 0, 0).unwrap()));
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+
+use serde::Serialize;
+use serenity::json::Value;
+
+use crate::entity::entity::Entity;
+
+#[derive(Default)]
+pub struct Change {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+pub struct EntityDiff {
+    pub teams: Change,
+    pub todos: Change,
+    pub schedules: Change,
+    pub meta_data_changed: bool,
+}
+
+impl EntityDiff {
+    pub fn between(old: &Entity, new: &Entity) -> Self {
+        EntityDiff {
+            teams: diff(&old.teams, &new.teams, |team| team.name.clone()),
+            todos: diff(&old.todos, &new.todos, |todo| todo.id),
+            schedules: diff(&old.schedules, &new.schedules, |schedule| schedule.content.clone()),
+            meta_data_changed: serenity::json::to_value(&old.meta_data).ok() != serenity::json::to_value(&new.meta_data).ok(),
+        }
+    }
+
+    // 변경 기록용 한 줄 요약 ex. `투두 +1 ~2`
+    pub fn summary(&self) -> String {
+        let mut parts = vec![];
+        for (name, change) in [("팀", &self.teams), ("투두", &self.todos), ("일정", &self.schedules)] {
+            let mut part = String::new();
+            for (sign, count) in [("+", change.added), ("-", change.removed), ("~", change.changed)] {
+                if count > 0 {
+                    part.push_str(&format!(" {sign}{count}"));
+                }
+            }
+            if !part.is_empty() {
+                parts.push(format!("{name}{part}"));
+            }
+        }
+        if self.meta_data_changed {
+            parts.push("설정 변경".to_string());
+        }
+        if parts.is_empty() {
+            return "변경 없음".to_string();
+        }
+        parts.join(", ")
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "추가 {} / 삭제 {} / 변경 {}", self.added, self.removed, self.changed)
+    }
+}
+
+impl Display for EntityDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "**팀** {}", self.teams)?;
+        writeln!(f, "**투두** {}", self.todos)?;
+        write!(f, "**일정** {}", self.schedules)?;
+        if self.meta_data_changed {
+            write!(f, "\n**설정** 변경됨")?;
+        }
+        Ok(())
+    }
+}
+
+// key가 같은 항목끼리 비교, 내용은 json 값으로 비교
+fn diff<T, K, F>(old: &[T], new: &[T], key: F) -> Change
+where
+    T: Serialize,
+    K: Eq + Hash,
+    F: Fn(&T) -> K,
+{
+    let to_map = |items: &[T]| -> HashMap<K, Value> {
+        items.iter()
+            .map(|item| (key(item), serenity::json::to_value(item).unwrap_or_default()))
+            .collect()
+    };
+    let (old, new) = (to_map(old), to_map(new));
+
+    let mut change = Change::default();
+    for (key, value) in &new {
+        match old.get(key) {
+            None => change.added += 1,
+            Some(old_value) if old_value != value => change.changed += 1,
+            _ => {}
+        }
+    }
+    change.removed = old.keys().filter(|key| !new.contains_key(key)).count();
+    change
+}
@@ -1,20 +1,39 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
+use serenity::json::Value;
+use crate::entity::migration::{migrate, CURRENT_SCHEMA_VERSION};
+use crate::entity::audit::AuditEntry;
 use crate::entity::metadata::MetaData;
 use crate::entity::schedule::Schedule;
 use crate::entity::team::Team;
-use crate::entity::todo::Todo;
+use crate::entity::todo::{Todo, TodoId};
 
 #[derive(Deserialize, Serialize, Clone)]
 pub struct Entity {
+    pub schema_version: u64,
     pub teams: Vec<Team>,
     pub todos: Vec<Todo>,
     pub meta_data: MetaData,
-    pub schedules: Vec<Schedule>
+    pub schedules: Vec<Schedule>,
+    // 저장될 때마다 1씩 증가, 동시에 수정한 경우를 감지하기 위해 사용
+    pub revision: u64,
+    // 최신 변경이 마지막, 최대 MAX_AUDIT_LOG개
+    pub audit_log: Vec<AuditEntry>,
+    // 다음에 추가될 투두의 id
+    pub next_todo_id: TodoId,
 }
 
 impl Entity {
+    // 이전 schema_version으로 저장된 json도 migration 후 읽습니다
+    pub fn from_json(json: &str) -> serenity::Result<Entity> {
+        let value: Value = serenity::json::from_str(json)?;
+        serenity::json::from_value(migrate(value)?)
+    }
+
     pub fn empty() -> Entity {
         Entity {
+            schema_version: CURRENT_SCHEMA_VERSION,
             teams: vec![
                 Team {
                     name: String::from("iOS"),
@@ -31,9 +50,15 @@ impl Entity {
             ],
             todos: vec![],
             meta_data: MetaData {
-                main_channel_id: None
+                main_channel_id: None,
+                log_channel_id: None,
+                timezone: None,
+                team_channels: BTreeMap::new(),
             },
-            schedules: vec![]
+            schedules: vec![],
+            revision: 0,
+            audit_log: vec![],
+            next_todo_id: 1,
         }
     }
 }
\ No newline at end of file
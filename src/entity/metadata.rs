@@ -1,8 +1,20 @@
+use std::collections::BTreeMap;
+
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 
 pub type ChannelId = u64;
 
+// 시간대를 설정하지 않은 guild의 시간대
+pub const DEFAULT_TIMEZONE: Tz = chrono_tz::Asia::Seoul;
+
 #[derive(Deserialize, Serialize, Clone)]
 pub struct MetaData {
-    pub main_channel_id: Option<ChannelId>
+    pub main_channel_id: Option<ChannelId>,
+    // 변경 기록을 함께 보낼 채널
+    pub log_channel_id: Option<ChannelId>,
+    // IANA 시간대 이름, 없으면 Asia/Seoul
+    pub timezone: Option<String>,
+    // 팀 이름 -> 투두 스레드를 만들 채널, 없으면 메인 채널
+    pub team_channels: BTreeMap<String, ChannelId>,
 }
\ No newline at end of file
@@ -0,0 +1,288 @@
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use serenity::json::{JsonMap, Value};
+use serenity::{Error, Result};
+use crate::entity::deadline::Deadline;
+use crate::entity::metadata::DEFAULT_TIMEZONE;
+
+pub const CURRENT_SCHEMA_VERSION: u64 = 11;
+
+type Migration = fn(&mut JsonMap) -> Result<()>;
+
+// MIGRATIONS[n]은 schema_version n -> n + 1 로 올립니다
+const MIGRATIONS: [Migration; CURRENT_SCHEMA_VERSION as usize] = [
+    migrate_v0_to_v1,
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+    migrate_v4_to_v5,
+    migrate_v5_to_v6,
+    migrate_v6_to_v7,
+    migrate_v7_to_v8,
+    migrate_v8_to_v9,
+    migrate_v9_to_v10,
+    migrate_v10_to_v11,
+];
+
+// 저장된 json을 현재 schema_version까지 한 단계씩 올립니다
+pub fn migrate(mut value: Value) -> Result<Value> {
+    let object = value.as_object_mut()
+        .ok_or_else(|| Error::Other("데이터베이스 형식이 올바르지 않습니다"))?;
+
+    // schema_version이 없으면 버전 관리 이전의 데이터
+    let version = match object.get("schema_version") {
+        None => 0,
+        Some(version) => version.as_u64()
+            .ok_or_else(|| Error::Other("schema_version이 올바르지 않습니다"))?
+    };
+    if version > CURRENT_SCHEMA_VERSION {
+        Err(Error::Other("더 새로운 버전의 데이터입니다. 봇을 업데이트해주세요"))?
+    }
+
+    for migration in &MIGRATIONS[version as usize..] {
+        migration(object)?;
+    }
+    object.insert("schema_version".to_string(), Value::from(CURRENT_SCHEMA_VERSION));
+    Ok(value)
+}
+
+// v0: 초기 버전, schedules가 없는 데이터가 있음
+// v1: schema_version, revision 추가
+fn migrate_v0_to_v1(object: &mut JsonMap) -> Result<()> {
+    object.entry("schedules").or_insert_with(|| Value::Array(vec![]));
+    object.entry("revision").or_insert_with(|| Value::from(0));
+    Ok(())
+}
+
+// v2: audit_log, meta_data.log_channel_id 추가
+fn migrate_v1_to_v2(object: &mut JsonMap) -> Result<()> {
+    object.entry("audit_log").or_insert_with(|| Value::Array(vec![]));
+    if let Some(meta_data) = object.get_mut("meta_data").and_then(|meta_data| meta_data.as_object_mut()) {
+        meta_data.entry("log_channel_id").or_insert(Value::Null);
+    }
+    Ok(())
+}
+
+// v3: todo.id, next_todo_id 추가, 기존 투두는 저장된 순서대로 1부터
+fn migrate_v2_to_v3(object: &mut JsonMap) -> Result<()> {
+    let mut next_todo_id: u64 = 1;
+    if let Some(todos) = object.get_mut("todos").and_then(|todos| todos.as_array_mut()) {
+        for todo in todos.iter_mut() {
+            let todo = todo.as_object_mut()
+                .ok_or_else(|| Error::Other("todo 형식이 올바르지 않습니다"))?;
+            todo.insert("id".to_string(), Value::from(next_todo_id));
+            next_todo_id += 1;
+        }
+    }
+    object.insert("next_todo_id".to_string(), Value::from(next_todo_id));
+    Ok(())
+}
+
+// v4: todo.todo.status 추가, 기존 투두는 모두 진행 전
+fn migrate_v3_to_v4(object: &mut JsonMap) -> Result<()> {
+    default_todo_field(object, "status", Value::from("todo"))
+}
+
+// v5: todo.todo.assignees 추가
+fn migrate_v4_to_v5(object: &mut JsonMap) -> Result<()> {
+    default_todo_field(object, "assignees", Value::Array(vec![]))
+}
+
+// v6: todo.todo.priority, todo.todo.labels 추가
+fn migrate_v5_to_v6(object: &mut JsonMap) -> Result<()> {
+    default_todo_field(object, "priority", Value::from("normal"))?;
+    default_todo_field(object, "labels", Value::Array(vec![]))
+}
+
+// v7: todo.todo.checklist 추가
+fn migrate_v6_to_v7(object: &mut JsonMap) -> Result<()> {
+    default_todo_field(object, "checklist", Value::Array(vec![]))
+}
+
+// v8: todo.todo.recurrence 추가
+fn migrate_v7_to_v8(object: &mut JsonMap) -> Result<()> {
+    default_todo_field(object, "recurrence", Value::Null)
+}
+
+// v9: meta_data.timezone 추가, null이면 기본 시간대
+fn migrate_v8_to_v9(object: &mut JsonMap) -> Result<()> {
+    if let Some(meta_data) = object.get_mut("meta_data").and_then(|meta_data| meta_data.as_object_mut()) {
+        meta_data.entry("timezone").or_insert(Value::Null);
+    }
+    Ok(())
+}
+
+// v10: todo.todo.deadline, schedules.deadline을 날짜에서 시각으로
+// 기존 마감기한은 guild 시간대로 그 날이 끝날 때까지
+fn migrate_v9_to_v10(object: &mut JsonMap) -> Result<()> {
+    let timezone: Tz = object.get("meta_data")
+        .and_then(|meta_data| meta_data.get("timezone"))
+        .and_then(|timezone| timezone.as_str())
+        .and_then(|timezone| timezone.parse().ok())
+        .unwrap_or(DEFAULT_TIMEZONE);
+    for_each_todo_content(object, |content| migrate_deadline(content, timezone))?;
+    if let Some(schedules) = object.get_mut("schedules").and_then(|schedules| schedules.as_array_mut()) {
+        for schedule in schedules.iter_mut() {
+            let schedule = schedule.as_object_mut()
+                .ok_or_else(|| Error::Other("schedule 형식이 올바르지 않습니다"))?;
+            migrate_deadline(schedule, timezone)?;
+        }
+    }
+    Ok(())
+}
+
+fn migrate_deadline(object: &mut JsonMap, timezone: Tz) -> Result<()> {
+    let date: NaiveDate = object.get("deadline")
+        .and_then(|deadline| deadline.as_str())
+        .and_then(|deadline| deadline.parse().ok())
+        .ok_or_else(|| Error::Other("마감기한 형식이 올바르지 않습니다"))?;
+    object.insert("deadline".to_string(), serenity::json::to_value(Deadline::date(date, timezone))?);
+    Ok(())
+}
+
+// v11: todo.todo.thread_id, meta_data.team_channels 추가
+fn migrate_v10_to_v11(object: &mut JsonMap) -> Result<()> {
+    default_todo_field(object, "thread_id", Value::Null)?;
+    if let Some(meta_data) = object.get_mut("meta_data").and_then(|meta_data| meta_data.as_object_mut()) {
+        meta_data.entry("team_channels").or_insert_with(|| Value::Object(JsonMap::new()));
+    }
+    Ok(())
+}
+
+// 모든 todo.todo에 f를 적용합니다
+fn for_each_todo_content(object: &mut JsonMap, mut f: impl FnMut(&mut JsonMap) -> Result<()>) -> Result<()> {
+    if let Some(todos) = object.get_mut("todos").and_then(|todos| todos.as_array_mut()) {
+        for todo in todos.iter_mut() {
+            let content = todo.get_mut("todo")
+                .and_then(|content| content.as_object_mut())
+                .ok_or_else(|| Error::Other("todo 형식이 올바르지 않습니다"))?;
+            f(content)?;
+        }
+    }
+    Ok(())
+}
+
+// 새로 추가된 todo.todo 필드의 기본값, 이미 있으면 그대로 둠
+fn default_todo_field(object: &mut JsonMap, key: &str, value: Value) -> Result<()> {
+    for_each_todo_content(object, |content| {
+        content.entry(key).or_insert_with(|| value.clone());
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serenity::json::json;
+
+    use crate::entity::entity::Entity;
+    use crate::entity::todo::{TodoPriority, TodoStatus};
+    use super::*;
+
+    #[test]
+    fn migrates_v0_without_schedules() {
+        let value = json!({
+            "teams": [{ "name": "iOS" }],
+            "todos": [{ "team": { "name": "iOS" }, "todo": { "content": "Auth 기능 구현", "deadline": "2024-03-02" } }],
+            "meta_data": { "main_channel_id": null }
+        });
+        let entity: Entity = serenity::json::from_value(migrate(value).unwrap()).unwrap();
+        assert_eq!(entity.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(entity.revision, 0);
+        assert_eq!(entity.todos.len(), 1);
+        assert_eq!(entity.todos[0].id, 1);
+        assert_eq!(entity.next_todo_id, 2);
+        assert!(entity.schedules.is_empty());
+    }
+
+    #[test]
+    fn migrates_v0_with_schedules() {
+        let value = json!({
+            "teams": [],
+            "todos": [],
+            "meta_data": { "main_channel_id": 1234 },
+            "schedules": [{ "content": "해커톤", "deadline": "2024-08-01" }]
+        });
+        let entity: Entity = serenity::json::from_value(migrate(value).unwrap()).unwrap();
+        assert_eq!(entity.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(entity.meta_data.main_channel_id, Some(1234));
+        assert_eq!(entity.schedules.len(), 1);
+        assert_eq!(entity.schedules[0].deadline.at.to_rfc3339(), "2024-08-01T23:59:59+09:00");
+    }
+
+    #[test]
+    fn migrates_v1() {
+        let value = json!({
+            "schema_version": 1,
+            "revision": 7,
+            "teams": [],
+            "todos": [],
+            "meta_data": { "main_channel_id": null },
+            "schedules": []
+        });
+        let entity: Entity = serenity::json::from_value(migrate(value).unwrap()).unwrap();
+        assert_eq!(entity.schema_version, CURRENT_SCHEMA_VERSION);
+        assert_eq!(entity.revision, 7);
+        assert!(entity.audit_log.is_empty());
+        assert_eq!(entity.meta_data.log_channel_id, None);
+        assert_eq!(entity.meta_data.timezone, None);
+    }
+
+    #[test]
+    fn migrates_v2_todo_ids() {
+        let todo = json!({ "team": { "name": "iOS" }, "todo": { "content": "Auth 기능 구현", "deadline": "2024-03-02" } });
+        let value = json!({
+            "schema_version": 2,
+            "revision": 3,
+            "teams": [],
+            "todos": [todo.clone(), todo.clone(), todo],
+            "meta_data": { "main_channel_id": null, "log_channel_id": null },
+            "schedules": [],
+            "audit_log": []
+        });
+        let entity: Entity = serenity::json::from_value(migrate(value).unwrap()).unwrap();
+        let ids: Vec<u64> = entity.todos.iter().map(|todo| todo.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(entity.next_todo_id, 4);
+        assert!(entity.todos.iter().all(|todo| todo.todo.status == TodoStatus::Todo));
+        assert!(entity.todos.iter().all(|todo| todo.todo.assignees.is_empty()));
+        assert!(entity.todos.iter().all(|todo| todo.todo.priority == TodoPriority::Normal && todo.todo.labels.is_empty()));
+        assert!(entity.todos.iter().all(|todo| todo.todo.checklist.is_empty()));
+        assert!(entity.todos.iter().all(|todo| todo.todo.recurrence.is_none()));
+        assert!(entity.todos.iter().all(|todo| todo.todo.deadline.all_day && todo.todo.deadline.label() == "03/02"));
+        assert!(entity.todos.iter().all(|todo| todo.todo.thread_id.is_none()));
+        assert!(entity.meta_data.team_channels.is_empty());
+    }
+
+    #[test]
+    fn migrates_v9_deadline_in_guild_timezone() {
+        let value = json!({
+            "schema_version": 9,
+            "revision": 1,
+            "teams": [],
+            "todos": [],
+            "meta_data": { "main_channel_id": null, "log_channel_id": null, "timezone": "America/New_York" },
+            "schedules": [{ "content": "해커톤", "deadline": "2024-08-01" }],
+            "audit_log": [],
+            "next_todo_id": 1
+        });
+        let entity: Entity = serenity::json::from_value(migrate(value).unwrap()).unwrap();
+        assert_eq!(entity.schedules[0].deadline.at.to_rfc3339(), "2024-08-01T23:59:59-04:00");
+    }
+
+    #[test]
+    fn keeps_current_version() {
+        let value = serenity::json::to_value(Entity::empty()).unwrap();
+        assert_eq!(migrate(value.clone()).unwrap(), value);
+    }
+
+    #[test]
+    fn rejects_newer_version() {
+        let value = json!({ "schema_version": CURRENT_SCHEMA_VERSION + 1 });
+        assert!(migrate(value).is_err());
+    }
+
+    #[test]
+    fn rejects_non_object() {
+        assert!(migrate(json!([])).is_err());
+    }
+}
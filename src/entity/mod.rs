@@ -3,3 +3,7 @@ pub mod team;
 pub mod entity;
 pub mod metadata;
 pub mod schedule;
+pub mod migration;
+pub mod diff;
+pub mod audit;
+pub mod deadline;
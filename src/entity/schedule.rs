@@ -1,8 +1,15 @@
-use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use crate::entity::deadline::Deadline;
 
 #[derive(Deserialize, Serialize, Clone)]
 pub struct Schedule {
     pub content: String,
-    pub deadline: NaiveDate
-}
\ No newline at end of file
+    pub deadline: Deadline
+}
+
+impl Schedule {
+    // ex. `03/02 데모데이`
+    pub fn label(&self) -> String {
+        format!("{} {}", self.deadline.label(), self.content)
+    }
+}
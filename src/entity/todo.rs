@@ -1,9 +1,17 @@
-use chrono::{Local, NaiveDate};
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
+use crate::entity::audit::UserId;
+use crate::entity::deadline::Deadline;
+use crate::entity::metadata::ChannelId;
 use crate::entity::team::Team;
 
+pub type TodoId = u64;
+
 #[derive(Deserialize, Serialize, Clone)]
 pub struct Todo {
+    // 한 번 정해지면 바뀌지 않음, 삭제된 id도 다시 쓰지 않음
+    pub id: TodoId,
     pub team: Team,
     pub todo: TodoContent
 }
@@ -11,33 +19,299 @@ pub struct Todo {
 #[derive(Deserialize, Serialize, Clone)]
 pub struct TodoContent {
     pub content: String,
-    pub deadline: NaiveDate
+    pub deadline: Deadline,
+    pub status: TodoStatus,
+    // 담당자 디스코드 유저 id, 비어있으면 팀 전체
+    pub assignees: Vec<UserId>,
+    pub priority: TodoPriority,
+    // 자유롭게 붙이는 태그 ex. `bug`, `design`
+    pub labels: Vec<String>,
+    // 순서가 있는 하위 작업
+    pub checklist: Vec<Subtask>,
+    // 있으면 완료되거나 마감이 지났을 때 다음 마감기한으로 다시 생성
+    pub recurrence: Option<Recurrence>,
+    // 논의용 스레드, 완료되거나 삭제되면 보관
+    pub thread_id: Option<ChannelId>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Recurrence {
+    // weeks 주마다
+    Weekly { weeks: u32 },
+    // 매달 day일, 그 달에 없는 날이면 마지막 날
+    // 없으면 처음 다시 만들 때 마감일의 날짜로 정해짐
+    Monthly {
+        #[serde(default)]
+        day: Option<u32>,
+    },
+}
+
+impl Recurrence {
+    // 매달 반복할 날짜를 아직 정하지 않았다면 deadline의 날짜로 고정합니다
+    pub fn anchored(self, deadline: Deadline, timezone: Tz) -> Recurrence {
+        match self {
+            Recurrence::Monthly { day: None } => Recurrence::Monthly { day: Some(deadline.local_date(timezone).day()) },
+            _ => self,
+        }
+    }
+
+    // guild 시간대 기준으로 날짜만 옮기고 시각은 그대로 둡니다
+    pub fn next_deadline(self, deadline: Deadline, timezone: Tz) -> Deadline {
+        let date = deadline.local_date(timezone);
+        let next = match self {
+            Recurrence::Weekly { weeks } => date.checked_add_days(Days::new(7 * weeks.max(1) as u64)),
+            Recurrence::Monthly { day } => next_month(date, day.unwrap_or(date.day())),
+        };
+        match next {
+            Some(date) => deadline.with_local_date(date, timezone),
+            None => Deadline { at: DateTime::<Utc>::MAX_UTC.fixed_offset(), ..deadline }
+        }
+    }
+
+    pub fn description(self) -> String {
+        match self {
+            Recurrence::Weekly { weeks: 1 } => String::from("매주"),
+            Recurrence::Weekly { weeks } => format!("{weeks}주마다"),
+            Recurrence::Monthly { .. } => String::from("매달"),
+        }
+    }
+}
+
+// 다음 달의 day일, 31일처럼 없는 날이면 그 달의 마지막 날
+fn next_month(date: NaiveDate, day: u32) -> Option<NaiveDate> {
+    let first = date.with_day(1)?.checked_add_months(Months::new(1))?;
+    let last = first.checked_add_months(Months::new(1))?.pred_opt()?.day();
+    first.with_day(day.clamp(1, last))
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct Subtask {
+    pub content: String,
+    pub done: bool,
+}
+
+// 선언 순서가 낮은 중요도 -> 높은 중요도, 최소 중요도 필터에 사용
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoPriority {
+    Low,
+    Normal,
+    High,
+    Urgent,
+}
+
+impl TodoPriority {
+    // 명령어 선택지 값, serde 이름과 같음
+    pub fn parse(value: &str) -> Option<TodoPriority> {
+        match value {
+            "low" => Some(TodoPriority::Low),
+            "normal" => Some(TodoPriority::Normal),
+            "high" => Some(TodoPriority::High),
+            "urgent" => Some(TodoPriority::Urgent),
+            _ => None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TodoPriority::Low => "low",
+            TodoPriority::Normal => "normal",
+            TodoPriority::High => "high",
+            TodoPriority::Urgent => "urgent",
+        }
+    }
+
+    // 보통 중요도는 표시하지 않음
+    pub fn badge(self) -> &'static str {
+        match self {
+            TodoPriority::Low => " 🔽",
+            TodoPriority::Normal => "",
+            TodoPriority::High => " 🔼",
+            TodoPriority::Urgent => " 🔥",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoStatus {
+    Todo,
+    InProgress,
+    Done,
+}
+
+impl TodoStatus {
+    // 버튼을 누를 때마다 todo -> in progress -> done -> todo
+    pub fn next(self) -> TodoStatus {
+        match self {
+            TodoStatus::Todo => TodoStatus::InProgress,
+            TodoStatus::InProgress => TodoStatus::Done,
+            TodoStatus::Done => TodoStatus::Todo,
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            TodoStatus::Todo => "⬜",
+            TodoStatus::InProgress => "🔨",
+            TodoStatus::Done => "✅",
+        }
+    }
+}
+
+impl Todo {
+    // 자동완성 등에서 투두를 구분하기 위한 한 줄 ex. `[iOS] 03/02 Auth 기능 구현`
+    pub fn label(&self) -> String {
+        format!("[{}] {} {}", self.team.name, self.todo.deadline.label(), self.todo.content)
+    }
+}
+
+impl TodoContent {
+    // 담당자, 라벨, 체크리스트 없이 할 일 상태인 보통 중요도 투두
+    pub fn new(content: String, deadline: Deadline) -> Self {
+        TodoContent {
+            content,
+            deadline,
+            status: TodoStatus::Todo,
+            assignees: vec![],
+            priority: TodoPriority::Normal,
+            labels: vec![],
+            checklist: vec![],
+            recurrence: None,
+            thread_id: None,
+        }
+    }
+
+    // 담당자 멘션 ex. ` <@1> <@2>`, 없으면 빈 문자열
+    pub fn assignees_mention(&self) -> String {
+        self.assignees.iter().map(|user_id| format!(" <@{user_id}>")).collect()
+    }
+
+    // 스레드 링크 ex. ` 💬 <#1>`, 없으면 빈 문자열
+    pub fn thread_link(&self) -> String {
+        self.thread_id.map(|thread_id| format!(" 💬 <#{thread_id}>")).unwrap_or_default()
+    }
+
+    // 중요도, 반복, 라벨 ex. ` 🔥 🔁 `bug` `design``
+    pub fn badges(&self) -> String {
+        let recurrence = if self.recurrence.is_some() { " 🔁" } else { "" };
+        let labels: String = self.labels.iter().map(|label| format!(" `{label}`")).collect();
+        format!("{}{recurrence}{labels}", self.priority.badge())
+    }
+
+    // 체크리스트 진행도 ex. ` (2/5)`, 체크리스트가 없으면 빈 문자열
+    pub fn progress(&self) -> String {
+        if self.checklist.is_empty() {
+            return String::new();
+        }
+        let done = self.checklist.iter().filter(|subtask| subtask.done).count();
+        format!(" ({done}/{})", self.checklist.len())
+    }
+
+    // `bug, design` 형식, 공백과 중복은 제거
+    pub fn parse_labels(input: &str) -> Vec<String> {
+        let mut labels: Vec<String> = vec![];
+        for label in input.split(',').map(|label| label.trim().to_lowercase()) {
+            if !label.is_empty() && !labels.contains(&label) {
+                labels.push(label);
+            }
+        }
+        labels
+    }
 }
 
 pub trait VecTodoExtension {
-    fn message(&self, team_name: &String) -> String;
+    // 마감기한 순
+    fn message(&self, team_name: &str) -> String;
+    // 이미 정렬된 순서 그대로
+    fn message_in_order(&self, team_name: &str) -> String;
 }
 
 impl VecTodoExtension for Vec<Todo> {
-    fn message(&self, team_name: &String) -> String {
+    fn message(&self, team_name: &str) -> String {
+        let mut todos = self.clone();
+        todos.sort_by_key(|todo| todo.todo.deadline);
+        todos.message_in_order(team_name)
+    }
+
+    fn message_in_order(&self, team_name: &str) -> String {
         let mut message = String::new();
         message.push_str(format!("## {}\n", team_name).as_str());
-        let mut todos = self.clone();
-        todos.sort_by(|a, b| a.todo.deadline.cmp(&b.todo.deadline));
-        todos.iter().for_each(|todo| {
-            let now: NaiveDate = Local::now().naive_local().date();
-            let is_future = now < todo.todo.deadline;
-            let bracket = if is_future { "" } else { "~~" };
+        let now = Utc::now();
+        self.iter().for_each(|todo| {
+            // 완료한 투두만 취소선, 마감이 지났는데 끝나지 않은 투두는 따로 표시
+            let status = todo.todo.status;
+            let is_done = status == TodoStatus::Done;
+            let is_overdue = !is_done && todo.todo.deadline.is_over(now);
+            let bracket = if is_done { "~~" } else { "" };
+            let overdue = if is_overdue { " ⚠️ 마감 지남" } else { "" };
             let m = format!(
-                "### - {bracket}{} {}{bracket}\n",
-                todo.todo.deadline.format("%m/%d").to_string(),
+                "### - {} {bracket}{} {}{bracket}{}{}{overdue}{}{}\n",
+                status.emoji(),
+                todo.todo.deadline.timestamp(),
                 todo.todo.content,
+                todo.todo.progress(),
+                todo.todo.badges(),
+                todo.todo.assignees_mention(),
+                todo.todo.thread_link(),
             );
             message.push_str(&m);
         });
-        if todos.is_empty() {
+        if self.is_empty() {
             message.push_str("### 할 일이 없네요. 쉬세요! 🤩")
         };
         message
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveDateTime};
+    use chrono_tz::America::New_York;
+    use chrono_tz::Asia::Seoul;
+
+    use crate::entity::deadline::Deadline;
+    use crate::entity::todo::Recurrence;
+
+    fn date_time(year: i32, month: u32, day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn keeps_day_of_month() {
+        let deadline = Deadline::date(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(), Seoul);
+        let recurrence = Recurrence::Monthly { day: None }.anchored(deadline, Seoul);
+        assert_eq!(recurrence, Recurrence::Monthly { day: Some(31) });
+
+        // 짧은 달에 맞춰 당겨져도 다음 달에는 다시 31일
+        let mut deadline = deadline;
+        let mut labels = vec![];
+        for _ in 0..4 {
+            deadline = recurrence.next_deadline(deadline, Seoul);
+            labels.push(deadline.label());
+        }
+        assert_eq!(labels, vec!["02/29", "03/31", "04/30", "05/31"]);
+        assert!(deadline.all_day);
+    }
+
+    #[test]
+    fn reads_monthly_without_day() {
+        let recurrence: Recurrence = serenity::json::from_str(r#"{"kind":"monthly"}"#).unwrap();
+        assert_eq!(recurrence, Recurrence::Monthly { day: None });
+    }
+
+    #[test]
+    fn keeps_wall_clock_across_dst() {
+        // 2024-03-10에 서머타임 시작, 11-03에 끝남
+        let deadline = Deadline::date_time(date_time(2024, 3, 7, 18), New_York);
+        let next = Recurrence::Weekly { weeks: 1 }.next_deadline(deadline, New_York);
+        assert_eq!(next.at.naive_local(), date_time(2024, 3, 14, 18));
+        assert_eq!(next.at.offset().local_minus_utc(), -4 * 3600);
+
+        let deadline = Deadline::date_time(date_time(2024, 10, 15, 9), New_York);
+        let next = Recurrence::Monthly { day: Some(15) }.next_deadline(deadline, New_York);
+        assert_eq!(next.at.naive_local(), date_time(2024, 11, 15, 9));
+        assert_eq!(next.at.offset().local_minus_utc(), -5 * 3600);
+    }
+}
@@ -1,29 +1,43 @@
 use std::sync::Arc;
-use serenity::all::{Context, GuildId, Http};
+use serenity::all::{GuildId, UserId};
+use crate::config::config::BackupConfig;
+use crate::database::database::DatabaseTrait;
+use crate::global::discord_api::DiscordApi;
+
+// 변경을 일으킨 사용자와 명령어, 변경 기록에 사용
+#[derive(Clone)]
+pub struct Actor {
+    pub user_id: UserId,
+    pub command: String,
+}
 
 pub struct Discord {
-    pub ctx: Context,
+    pub api: Arc<dyn DiscordApi>,
     pub guild_id: GuildId,
+    pub database: Arc<dyn DatabaseTrait>,
+    pub backup: BackupConfig,
+    pub actor: Actor,
 }
 
 impl Discord {
-    pub fn new(ctx: Context, guild_id: GuildId) -> Self {
-        Discord { ctx, guild_id }
+    pub fn new(api: Arc<dyn DiscordApi>, guild_id: GuildId, database: Arc<dyn DatabaseTrait>, backup: BackupConfig, actor: Actor) -> Self {
+        Discord { api, guild_id, database, backup, actor }
     }
 }
 
 #[derive(Clone)]
 pub struct Guild {
-    pub http: Arc<Http>,
+    pub api: Arc<dyn DiscordApi>,
     pub guild_id: GuildId,
+    pub actor: Option<Actor>,
 }
 
 impl Guild {
-    pub fn new(http: Arc<Http>, guild_id: GuildId) -> Self {
-        Guild { http, guild_id }
+    pub fn new(api: Arc<dyn DiscordApi>, guild_id: GuildId) -> Self {
+        Guild { api, guild_id, actor: None }
     }
 
     pub fn from(discord: &Discord) -> Self {
-        Guild::new(discord.ctx.http.clone(), discord.guild_id)
+        Guild { api: discord.api.clone(), guild_id: discord.guild_id, actor: Some(discord.actor.clone()) }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,177 @@
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use serenity::all::{ActionRowComponent, ChannelId, CommandInteraction, ComponentInteraction, ComponentInteractionCollector, CreateActionRow, CreateChannel, CreateInputText, CreateInteractionResponse, CreateMessage, CreateModal, CreateThread, EditInteractionResponse, EditThread, GetMessages, GuildChannel, GuildId, Http, InteractionId, Message, MessageId, ModalInteractionCollector, ShardMessenger, UserId};
+use serenity::async_trait;
+use serenity::builder::Builder;
+use serenity::{Error, Result};
+
+// 응답에 필요한 interaction 정보
+#[derive(Clone)]
+pub struct InteractionHandle {
+    pub id: InteractionId,
+    pub token: String,
+}
+
+impl From<&CommandInteraction> for InteractionHandle {
+    fn from(command: &CommandInteraction) -> Self {
+        InteractionHandle { id: command.id, token: command.token.clone() }
+    }
+}
+
+impl From<&ComponentInteraction> for InteractionHandle {
+    fn from(component: &ComponentInteraction) -> Self {
+        InteractionHandle { id: component.id, token: component.token.clone() }
+    }
+}
+
+pub struct ModalResponse {
+    pub interaction: InteractionHandle,
+    pub inputs: Vec<String>,
+}
+
+pub struct ComponentResponse {
+    pub interaction: InteractionHandle,
+    pub custom_id: String,
+}
+
+// 봇이 사용하는 디스코드 기능, 테스트에서는 FakeDiscord로 대체합니다
+#[async_trait]
+pub trait DiscordApi: Send + Sync {
+    async fn get_bot_id(&self) -> Result<UserId>;
+    async fn get_guilds(&self) -> Result<Vec<GuildId>>;
+
+    async fn get_channels(&self, guild_id: GuildId) -> Result<Vec<GuildChannel>>;
+    async fn create_channel(&self, guild_id: GuildId, builder: CreateChannel<'_>) -> Result<GuildChannel>;
+    // 메세지에 연결되지 않은 스레드
+    async fn create_thread(&self, channel_id: ChannelId, builder: CreateThread<'_>) -> Result<GuildChannel>;
+    async fn edit_thread(&self, thread_id: ChannelId, builder: EditThread<'_>) -> Result<GuildChannel>;
+
+    // 최신 메세지부터
+    async fn get_messages(&self, channel_id: ChannelId, before: Option<MessageId>, limit: u8) -> Result<Vec<Message>>;
+    async fn send_message(&self, channel_id: ChannelId, builder: CreateMessage) -> Result<Message>;
+    async fn delete_message(&self, channel_id: ChannelId, message_id: MessageId) -> Result<()>;
+
+    async fn create_response(&self, interaction: &InteractionHandle, builder: CreateInteractionResponse) -> Result<()>;
+    async fn get_response(&self, interaction: &InteractionHandle) -> Result<Message>;
+    async fn edit_response(&self, interaction: &InteractionHandle, builder: EditInteractionResponse) -> Result<Message>;
+
+    // 모달을 띄우고 제출될 때까지 기다립니다, 입력값은 fields 순서
+    async fn quick_modal(&self, interaction: &InteractionHandle, title: &str, fields: Vec<CreateInputText>, timeout: Duration) -> Result<Option<ModalResponse>>;
+    // message의 컴포넌트를 user가 누를 때까지 기다립니다
+    async fn await_component(&self, message_id: MessageId, user_id: UserId, timeout: Duration) -> Option<ComponentResponse>;
+}
+
+static BOT_ID: OnceLock<UserId> = OnceLock::new();
+
+pub struct SerenityApi {
+    http: Arc<Http>,
+    // 스케쥴러처럼 gateway가 없는 곳에서는 None, 이때는 모달/버튼을 기다릴 수 없음
+    shard: Option<ShardMessenger>,
+}
+
+impl SerenityApi {
+    pub fn new(http: Arc<Http>, shard: Option<ShardMessenger>) -> Self {
+        SerenityApi { http, shard }
+    }
+}
+
+#[async_trait]
+impl DiscordApi for SerenityApi {
+    async fn get_bot_id(&self) -> Result<UserId> {
+        if let Some(bot_id) = BOT_ID.get() {
+            return Ok(*bot_id);
+        }
+        let bot_id = self.http.get_current_user().await?.id;
+        Ok(*BOT_ID.get_or_init(|| bot_id))
+    }
+
+    async fn get_guilds(&self) -> Result<Vec<GuildId>> {
+        let guilds = self.http.get_guilds(None, None).await?;
+        Ok(guilds.iter().map(|guild| guild.id).collect())
+    }
+
+    async fn get_channels(&self, guild_id: GuildId) -> Result<Vec<GuildChannel>> {
+        let channels = guild_id.channels(&self.http).await?;
+        Ok(channels.into_values().collect())
+    }
+
+    async fn create_channel(&self, guild_id: GuildId, builder: CreateChannel<'_>) -> Result<GuildChannel> {
+        guild_id.create_channel(&self.http, builder).await
+    }
+
+    async fn create_thread(&self, channel_id: ChannelId, builder: CreateThread<'_>) -> Result<GuildChannel> {
+        channel_id.create_thread(&self.http, builder).await
+    }
+
+    async fn edit_thread(&self, thread_id: ChannelId, builder: EditThread<'_>) -> Result<GuildChannel> {
+        thread_id.edit_thread(&self.http, builder).await
+    }
+
+    async fn get_messages(&self, channel_id: ChannelId, before: Option<MessageId>, limit: u8) -> Result<Vec<Message>> {
+        let mut builder = GetMessages::new().limit(limit);
+        if let Some(before) = before {
+            builder = builder.before(before);
+        }
+        channel_id.messages(&self.http, builder).await
+    }
+
+    async fn send_message(&self, channel_id: ChannelId, builder: CreateMessage) -> Result<Message> {
+        channel_id.send_message(&self.http, builder).await
+    }
+
+    async fn delete_message(&self, channel_id: ChannelId, message_id: MessageId) -> Result<()> {
+        channel_id.delete_message(&self.http, message_id).await
+    }
+
+    async fn create_response(&self, interaction: &InteractionHandle, builder: CreateInteractionResponse) -> Result<()> {
+        builder.execute(self.http.as_ref(), (interaction.id, &interaction.token)).await
+    }
+
+    async fn get_response(&self, interaction: &InteractionHandle) -> Result<Message> {
+        self.http.get_original_interaction_response(&interaction.token).await
+    }
+
+    async fn edit_response(&self, interaction: &InteractionHandle, builder: EditInteractionResponse) -> Result<Message> {
+        builder.execute(self.http.as_ref(), &interaction.token).await
+    }
+
+    async fn quick_modal(&self, interaction: &InteractionHandle, title: &str, fields: Vec<CreateInputText>, timeout: Duration) -> Result<Option<ModalResponse>> {
+        let shard = self.shard.as_ref().ok_or_else(|| Error::Other("모달을 기다릴 수 없습니다"))?;
+
+        // serenity의 quick_modal과 같은 방식, custom id는 interaction id와 field 순서
+        let custom_id = interaction.id.to_string();
+        let rows = fields.into_iter()
+            .enumerate()
+            .map(|(index, field)| CreateActionRow::InputText(field.custom_id(index.to_string())))
+            .collect();
+        let modal = CreateModal::new(&custom_id, title).components(rows);
+        self.create_response(interaction, CreateInteractionResponse::Modal(modal)).await?;
+
+        let modal_interaction = ModalInteractionCollector::new(shard)
+            .custom_ids(vec![custom_id])
+            .timeout(timeout)
+            .next()
+            .await;
+        let Some(modal_interaction) = modal_interaction else { return Ok(None) };
+
+        let inputs = modal_interaction.data.components.iter()
+            .filter_map(|row| match row.components.first() {
+                Some(ActionRowComponent::InputText(text)) => Some(text.value.clone().unwrap_or_default()),
+                _ => None
+            })
+            .collect();
+        let interaction = InteractionHandle { id: modal_interaction.id, token: modal_interaction.token.clone() };
+        Ok(Some(ModalResponse { interaction, inputs }))
+    }
+
+    async fn await_component(&self, message_id: MessageId, user_id: UserId, timeout: Duration) -> Option<ComponentResponse> {
+        let interaction = ComponentInteractionCollector::new(self.shard.as_ref()?)
+            .message_id(message_id)
+            .author_id(user_id)
+            .timeout(timeout)
+            .next()
+            .await?;
+        Some(ComponentResponse { interaction: (&interaction).into(), custom_id: interaction.data.custom_id })
+    }
+}
@@ -0,0 +1,368 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serenity::all::{ChannelId, ChannelType, CommandInteraction, ComponentInteraction, CreateChannel, CreateInputText, CreateThread, CreateInteractionResponse, CreateMessage, EditInteractionResponse, EditThread, GuildChannel, GuildId, InteractionId, Message, MessageId, UserId};
+use serenity::async_trait;
+use serenity::json::{json, Value};
+use serenity::{Error, Result};
+
+use chrono::NaiveDate;
+
+use crate::config::config::{BackupConfig, Codec};
+use crate::database::database::{DatabaseTrait, DATABASE_CHANNEL};
+use crate::database::shard;
+use crate::database::todo_repo::TodoRepo;
+use crate::entity::deadline::Deadline;
+use crate::entity::entity::Entity;
+use crate::entity::metadata::DEFAULT_TIMEZONE;
+use crate::entity::team::Team;
+use crate::entity::todo::TodoContent;
+use crate::util::json::decode;
+use crate::global::discord::{Actor, Discord, Guild};
+use crate::global::discord_api::{ComponentResponse, DiscordApi, InteractionHandle, ModalResponse};
+
+pub const BOT_ID: UserId = UserId::new(1);
+pub const GUILD_ID: GuildId = GuildId::new(10);
+pub const USER_ID: UserId = UserId::new(100);
+
+// 테스트용 디스코드, 채널과 메세지를 메모리에 들고 있고 응답은 json으로 기록합니다
+pub struct FakeDiscord {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    next_id: u64,
+    guilds: Vec<GuildId>,
+    channels: Vec<GuildChannel>,
+    messages: Vec<Message>,
+    archived_threads: Vec<ChannelId>,
+    // Some(n)이면 n개를 보낸 뒤부터 메세지 전송이 실패
+    sends_before_failure: Option<usize>,
+    responses: Vec<Value>,
+    modal_inputs: VecDeque<Vec<String>>,
+    components: VecDeque<String>,
+}
+
+impl State {
+    fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        1000 + self.next_id
+    }
+}
+
+impl FakeDiscord {
+    pub fn new() -> Self {
+        let state = State { guilds: vec![GUILD_ID], ..Default::default() };
+        FakeDiscord { state: Mutex::new(state) }
+    }
+
+    pub fn add_channel(&self, name: &str) -> ChannelId {
+        let mut state = self.state.lock().unwrap();
+        let id = ChannelId::new(state.next_id());
+        state.channels.push(new_channel(id, GUILD_ID, name));
+        id
+    }
+
+    pub fn find_channel(&self, name: &str) -> Option<ChannelId> {
+        let state = self.state.lock().unwrap();
+        state.channels.iter().find(|channel| channel.name == name).map(|channel| channel.id)
+    }
+
+    // 오래된 순
+    pub fn message_contents(&self, channel_id: ChannelId) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+        state.messages.iter()
+            .filter(|message| message.channel_id == channel_id)
+            .map(|message| message.content.clone())
+            .collect()
+    }
+
+    // database 채널에 저장된 Entity
+    pub fn stored_entity(&self) -> Entity {
+        let channel_id = self.find_channel(DATABASE_CHANNEL).expect("database 채널이 없습니다");
+        let contents = self.message_contents(channel_id);
+        let contents: Vec<&str> = contents.iter().map(|content| content.as_str()).collect();
+        Entity::from_json(&decode(&shard::join(&contents).unwrap()).unwrap()).unwrap()
+    }
+
+    // 쓰다가 끊기는 상황을 흉내내기 위해 count개를 보낸 뒤부터 전송을 실패시킴
+    pub fn fail_sends_after(&self, count: usize) {
+        self.state.lock().unwrap().sends_before_failure = Some(count);
+    }
+
+    pub fn is_archived(&self, thread_id: ChannelId) -> bool {
+        self.state.lock().unwrap().archived_threads.contains(&thread_id)
+    }
+
+    pub fn responses(&self) -> Vec<Value> {
+        self.state.lock().unwrap().responses.clone()
+    }
+
+    // 다음 모달에 입력될 값
+    pub fn push_modal_inputs(&self, inputs: &[&str]) {
+        let inputs = inputs.iter().map(|input| input.to_string()).collect();
+        self.state.lock().unwrap().modal_inputs.push_back(inputs);
+    }
+
+    // 다음에 눌릴 버튼
+    pub fn push_component(&self, custom_id: &str) {
+        self.state.lock().unwrap().components.push_back(custom_id.to_string());
+    }
+
+    fn new_handle(&self) -> InteractionHandle {
+        let id = self.state.lock().unwrap().next_id();
+        InteractionHandle { id: InteractionId::new(id), token: format!("token-{id}") }
+    }
+}
+
+#[async_trait]
+impl DiscordApi for FakeDiscord {
+    async fn get_bot_id(&self) -> Result<UserId> {
+        Ok(BOT_ID)
+    }
+
+    async fn get_guilds(&self) -> Result<Vec<GuildId>> {
+        Ok(self.state.lock().unwrap().guilds.clone())
+    }
+
+    async fn get_channels(&self, guild_id: GuildId) -> Result<Vec<GuildChannel>> {
+        let state = self.state.lock().unwrap();
+        Ok(state.channels.iter().filter(|channel| channel.guild_id == guild_id).cloned().collect())
+    }
+
+    async fn create_channel(&self, guild_id: GuildId, builder: CreateChannel<'_>) -> Result<GuildChannel> {
+        let value = serenity::json::to_value(builder)?;
+        let name = value["name"].as_str().unwrap_or_default();
+        let mut state = self.state.lock().unwrap();
+        let channel = new_channel(ChannelId::new(state.next_id()), guild_id, name);
+        state.channels.push(channel.clone());
+        Ok(channel)
+    }
+
+    async fn create_thread(&self, channel_id: ChannelId, builder: CreateThread<'_>) -> Result<GuildChannel> {
+        let value = serenity::json::to_value(builder)?;
+        let name = value["name"].as_str().unwrap_or_default();
+        let mut state = self.state.lock().unwrap();
+        let guild_id = state.channels.iter()
+            .find(|channel| channel.id == channel_id)
+            .map(|channel| channel.guild_id)
+            .ok_or_else(|| Error::Other("채널을 찾을 수 없습니다"))?;
+        let mut thread = new_channel(ChannelId::new(state.next_id()), guild_id, name);
+        thread.kind = ChannelType::PublicThread;
+        thread.parent_id = Some(channel_id);
+        state.channels.push(thread.clone());
+        Ok(thread)
+    }
+
+    async fn edit_thread(&self, thread_id: ChannelId, builder: EditThread<'_>) -> Result<GuildChannel> {
+        let value = serenity::json::to_value(builder)?;
+        let mut state = self.state.lock().unwrap();
+        let thread = state.channels.iter()
+            .find(|channel| channel.id == thread_id && channel.kind == ChannelType::PublicThread)
+            .cloned()
+            .ok_or_else(|| Error::Other("스레드를 찾을 수 없습니다"))?;
+        match value["archived"].as_bool() {
+            Some(true) => state.archived_threads.push(thread_id),
+            Some(false) => state.archived_threads.retain(|id| *id != thread_id),
+            None => {}
+        }
+        Ok(thread)
+    }
+
+    async fn get_messages(&self, channel_id: ChannelId, before: Option<MessageId>, limit: u8) -> Result<Vec<Message>> {
+        let state = self.state.lock().unwrap();
+        Ok(state.messages.iter()
+            .rev()
+            .filter(|message| message.channel_id == channel_id)
+            .filter(|message| before.is_none_or(|before| message.id < before))
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn send_message(&self, channel_id: ChannelId, builder: CreateMessage) -> Result<Message> {
+        let value = serenity::json::to_value(builder)?;
+        let content = value["content"].as_str().unwrap_or_default();
+        let mut state = self.state.lock().unwrap();
+        match state.sends_before_failure {
+            Some(0) => return Err(Error::Other("메세지를 보낼 수 없습니다")),
+            Some(count) => state.sends_before_failure = Some(count - 1),
+            None => {}
+        }
+        let message = new_message(MessageId::new(state.next_id()), channel_id, BOT_ID, content);
+        state.messages.push(message.clone());
+        Ok(message)
+    }
+
+    async fn delete_message(&self, channel_id: ChannelId, message_id: MessageId) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.messages.retain(|message| !(message.channel_id == channel_id && message.id == message_id));
+        Ok(())
+    }
+
+    async fn create_response(&self, _interaction: &InteractionHandle, builder: CreateInteractionResponse) -> Result<()> {
+        let value = serenity::json::to_value(builder)?;
+        self.state.lock().unwrap().responses.push(value);
+        Ok(())
+    }
+
+    async fn get_response(&self, _interaction: &InteractionHandle) -> Result<Message> {
+        let mut state = self.state.lock().unwrap();
+        Ok(new_message(MessageId::new(state.next_id()), ChannelId::new(1), BOT_ID, ""))
+    }
+
+    async fn edit_response(&self, _interaction: &InteractionHandle, builder: EditInteractionResponse) -> Result<Message> {
+        let value = serenity::json::to_value(builder)?;
+        let mut state = self.state.lock().unwrap();
+        state.responses.push(value);
+        Ok(new_message(MessageId::new(state.next_id()), ChannelId::new(1), BOT_ID, ""))
+    }
+
+    async fn quick_modal(&self, _interaction: &InteractionHandle, title: &str, _fields: Vec<CreateInputText>, _timeout: Duration) -> Result<Option<ModalResponse>> {
+        let inputs = {
+            let mut state = self.state.lock().unwrap();
+            state.responses.push(json!({ "type": 9, "data": { "title": title } }));
+            state.modal_inputs.pop_front()
+        };
+        Ok(inputs.map(|inputs| ModalResponse { interaction: self.new_handle(), inputs }))
+    }
+
+    async fn await_component(&self, _message_id: MessageId, _user_id: UserId, _timeout: Duration) -> Option<ComponentResponse> {
+        let custom_id = self.state.lock().unwrap().components.pop_front()?;
+        Some(ComponentResponse { interaction: self.new_handle(), custom_id })
+    }
+}
+
+// USER_ID가 command를 실행한 Discord
+pub fn discord(api: Arc<FakeDiscord>, database: Arc<dyn DatabaseTrait>, command: &str) -> Discord {
+    let actor = Actor { user_id: USER_ID, command: command.to_string() };
+    Discord::new(api, GUILD_ID, database, backup_config(), actor)
+}
+
+// 테스트 투두의 마감일, 2024-03-02
+pub fn test_deadline() -> Deadline {
+    Deadline::date(NaiveDate::from_ymd_opt(2024, 3, 2).unwrap(), DEFAULT_TIMEZONE)
+}
+
+// test_deadline까지인 투두 내용
+pub fn test_content(content: &str) -> TodoContent {
+    TodoContent::new(content.to_string(), test_deadline())
+}
+
+// team 팀에 투두를 추가합니다, id는 1부터 순서대로
+pub async fn create_test_todo(api: &Arc<FakeDiscord>, database: &Arc<dyn DatabaseTrait>, team: &str, content: &TodoContent) {
+    TodoRepo::new(Guild::new(api.clone(), GUILD_ID), database.clone())
+        .create_todo(&Team { name: team.to_string() }, content).await.unwrap();
+}
+
+// 테스트마다 다른 임시 폴더에 스냅샷을 저장합니다
+pub fn backup_config() -> BackupConfig {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let name = format!("mowgli-backups-{}-{}", std::process::id(), NEXT.fetch_add(1, Ordering::Relaxed));
+    let path = std::env::temp_dir().join(name).to_string_lossy().to_string();
+    BackupConfig { path, retention: 3, codec: Codec::Json, secrets: vec![] }
+}
+
+fn new_channel(id: ChannelId, guild_id: GuildId, name: &str) -> GuildChannel {
+    let mut channel = GuildChannel::default();
+    channel.id = id;
+    channel.guild_id = guild_id;
+    channel.name = name.to_string();
+    channel.kind = ChannelType::Text;
+    channel
+}
+
+fn new_message(id: MessageId, channel_id: ChannelId, author_id: UserId, content: &str) -> Message {
+    let mut message = Message::default();
+    message.id = id;
+    message.channel_id = channel_id;
+    message.guild_id = Some(GUILD_ID);
+    message.author.id = author_id;
+    message.content = content.to_string();
+    message
+}
+
+// `/{name} {sub_command}` 명령어, options는 하위 옵션 json 배열
+pub fn command_interaction(name: &str, sub_command: &str, options: Value) -> CommandInteraction {
+    serenity::json::from_value(json!({
+        "id": "2000",
+        "application_id": "3",
+        "type": 2,
+        "data": {
+            "id": "4",
+            "name": name,
+            "type": 1,
+            "options": [{ "name": sub_command, "type": 1, "options": options }]
+        },
+        "guild_id": GUILD_ID.to_string(),
+        "channel_id": "5",
+        "member": interaction_member(),
+        "token": "command-token",
+        "version": 1,
+        "app_permissions": "0",
+        "locale": "ko",
+        "guild_locale": "ko",
+        "entitlements": [],
+        "attachment_size_limit": 0
+    })).unwrap()
+}
+
+// interaction_name 명령어의 응답 메세지에 달린 버튼을 누른 경우
+pub fn component_interaction(interaction_name: &str, custom_id: &str) -> ComponentInteraction {
+    component_interaction_with(interaction_name, json!({ "custom_id": custom_id, "component_type": 2 }))
+}
+
+// 유저 선택 메뉴에서 values를 고른 경우
+pub fn user_select_interaction(interaction_name: &str, custom_id: &str, values: &[UserId]) -> ComponentInteraction {
+    let values: Vec<String> = values.iter().map(|user_id| user_id.to_string()).collect();
+    component_interaction_with(interaction_name, json!({ "custom_id": custom_id, "component_type": 5, "values": values }))
+}
+
+fn component_interaction_with(interaction_name: &str, data: Value) -> ComponentInteraction {
+    serenity::json::from_value(json!({
+        "id": "2001",
+        "application_id": "3",
+        "type": 3,
+        "data": data,
+        "guild_id": GUILD_ID.to_string(),
+        "channel_id": "5",
+        "member": interaction_member(),
+        "token": "component-token",
+        "version": 1,
+        "message": serenity::json::to_value(interaction_message(interaction_name)).unwrap(),
+        "app_permissions": "0",
+        "locale": "ko",
+        "guild_locale": "ko",
+        "entitlements": [],
+        "attachment_size_limit": 0
+    })).unwrap()
+}
+
+fn interaction_member() -> Value {
+    json!({
+        "user": { "id": USER_ID.to_string(), "username": "mowgli", "discriminator": "0000", "global_name": null, "avatar": null },
+        "roles": [],
+        "joined_at": "2024-01-01T00:00:00Z",
+        "deaf": false,
+        "mute": false,
+        "flags": 0,
+        "permissions": "8"
+    })
+}
+
+fn interaction_message(interaction_name: &str) -> Message {
+    let mut message = new_message(MessageId::new(3000), ChannelId::new(5), BOT_ID, "");
+    #[allow(deprecated)]
+    {
+        message.interaction = serenity::json::from_value(json!({
+            "id": "2000",
+            "type": 2,
+            "name": interaction_name,
+            "user": { "id": USER_ID.to_string(), "username": "mowgli", "discriminator": "0000", "global_name": null, "avatar": null }
+        })).ok();
+    }
+    message
+}
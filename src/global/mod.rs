@@ -1 +1,4 @@
-pub mod discord;
\ No newline at end of file
+pub mod discord;
+pub mod discord_api;
+#[cfg(test)]
+pub mod fake_discord;
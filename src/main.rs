@@ -1,4 +1,7 @@
+#![allow(clippy::module_inception, clippy::result_large_err)]
+
 use std::io::Error;
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use serenity::{
     all::Message,
@@ -11,26 +14,47 @@ use serenity::{
     prelude::*,
     utils::MessageBuilder,
 };
-use serenity::all::{CommandInteraction, CommandOptionType, ComponentInteraction, CreateCommand, CreateCommandOption};
+use serenity::all::{ChannelId, CommandInteraction, CommandOptionType, ComponentInteraction, CreateCommand, CreateCommandOption, GuildId, MessageId, MessageUpdateEvent, Permissions};
 use serenity::builder::CreateInteractionResponse;
 use tokio_cron_scheduler::{JobScheduler, JobSchedulerError};
-use crate::commands::CommandTrait;
+use crate::commands::{AutocompleteTrait, CommandTrait};
+use crate::commands::checklist_todo_command::ChecklistTodoCommand;
+use crate::commands::create_schedule_command::CreateScheduleCommand;
 use crate::commands::create_todo_command::AskTeamCommand;
+use crate::commands::delete_todo_command::DeleteTodoCommand;
+use crate::commands::done_todo_command::DoneTodoCommand;
+use crate::commands::edit_todo_command::EditTodoCommand;
+use crate::commands::export_db_command::ExportDBCommand;
+use crate::commands::my_todos_command::MyTodosCommand;
 use crate::commands::force_import_db_command::ForceImportDBCommand;
+use crate::commands::get_schedule_command::GetScheduleCommand;
+use crate::commands::history_db_command::HistoryDBCommand;
 use crate::commands::not_found_command::NotFoundCommand;
 use crate::commands::remind_command::RemindCommand;
 use crate::commands::reset_db_command::ResetDBCommand;
 use crate::commands::reset_todos_command::ResetTodosCommand;
+use crate::commands::restore_db_command::RestoreDBCommand;
+use crate::commands::setting_log_channel_command::SettingLogChannelCommand;
 use crate::commands::setting_main_channel_command::SettingMainChannelCommand;
+use crate::commands::setting_team_channel_command::SettingTeamChannelCommand;
+use crate::commands::setting_timezone_command::SettingTimezoneCommand;
 use crate::commands::show_all_todos_command::ShowAllTodosCommand;
+use crate::commands::undo_command::UndoCommand;
 use crate::component::ComponentTrait;
+use crate::component::assign_todo_component::{AssignTodoComponent, TODO_ASSIGN_PREFIX};
+use crate::component::check_subtask_component::{CheckSubtaskComponent, TODO_CHECK_PREFIX};
 use crate::component::create_todo_component::CreateTodoComponent;
+use crate::component::page_component::PageComponent;
 use crate::component::show_todos_component::ShowTodosComponent;
+use crate::component::todo_status_component::{TodoStatusComponent, TODO_STATUS_PREFIX};
 use crate::component::not_found::NotFountComponent;
-use crate::config::config::Config;
-use crate::global::discord::Discord;
-use crate::schedule::schedule_task;
+use crate::config::config::{BackupConfig, Config};
+use crate::database::database::{create_database, DatabaseTrait};
+use crate::global::discord::{Actor, Discord};
+use crate::global::discord_api::{DiscordApi, SerenityApi};
+use crate::schedule::{backup_task, recurrence_task, schedule_task};
 use crate::util::create_embed_extension::{ResultCreateEmbed};
+use crate::util::page::PAGE_PREFIX;
 
 mod commands;
 mod database;
@@ -41,7 +65,10 @@ mod component;
 mod global;
 mod schedule;
 
-struct Handler;
+struct Handler {
+    database: Arc<dyn DatabaseTrait>,
+    backup: BackupConfig,
+}
 
 // 욕설 리스트
 static ARR: &[&str] = &[
@@ -61,18 +88,23 @@ static ARR: &[&str] = &[
 
 // impl for interaction_create fn
 impl Handler {
+    fn api(&self, ctx: &Context) -> Arc<dyn DiscordApi> {
+        Arc::new(SerenityApi::new(ctx.http.clone(), Some(ctx.shard.clone())))
+    }
+
     async fn handle_command_interaction(&self, ctx: &Context, command: &CommandInteraction) {
         // create discord
         let guild_id = match command.guild_id.ok_or_else(|| Error::other("guild id를 찾을 수 없습니다")) {
             Ok(v) => v,
             _ => return
         };
-        let discord = Discord::new(ctx.clone(), guild_id);
-
-        // handle command
         let data = &command.data;
         let name = data.name.as_str();
-        let option = data.options.first().unwrap().name.as_str();
+        let option = data.options.first().map(|option| option.name.as_str()).unwrap_or_default();
+        let actor = Actor { user_id: command.user.id, command: format!("/{} {}", name, option).trim_end().to_string() };
+        let discord = Discord::new(self.api(ctx), guild_id, self.database.clone(), self.backup.clone(), actor);
+
+        // handle command
         let result = match name {
             "todo" => match option {
                 "show" => AskTeamCommand::run(&discord, command).await,
@@ -80,62 +112,114 @@ impl Handler {
                 "reset" => ResetTodosCommand::run(&discord, command).await,
                 "add" => AskTeamCommand::run(&discord, command).await,
                 "remind" => RemindCommand::run(&discord, command).await,
+                "edit" => EditTodoCommand::run(&discord, command).await,
+                "delete" => DeleteTodoCommand::run(&discord, command).await,
+                "done" => DoneTodoCommand::run(&discord, command).await,
+                "mine" => MyTodosCommand::run(&discord, command).await,
+                "checklist" => ChecklistTodoCommand::run(&discord, command).await,
                 _ => NotFoundCommand::run(&discord, command).await
             },
             "설정" => match option {
                 "main-channel" => SettingMainChannelCommand::run(&discord, command).await,
+                "log-channel" => SettingLogChannelCommand::run(&discord, command).await,
+                "timezone" => SettingTimezoneCommand::run(&discord, command).await,
+                "team-channel" => SettingTeamChannelCommand::run(&discord, command).await,
                 _ => NotFoundCommand::run(&discord, command).await
             },
             "db" => match option {
                 "reset" => ResetDBCommand::run(&discord, command).await,
                 "force-import" => ForceImportDBCommand::run(&discord, command).await,
+                "export" => ExportDBCommand::run(&discord, command).await,
+                "restore" => RestoreDBCommand::run(&discord, command).await,
+                "history" => HistoryDBCommand::run(&discord, command).await,
                 _ => NotFoundCommand::run(&discord, command).await
             },
             "일정" => match option {
-                "add" => NotFoundCommand::run(&discord, command).await,
+                "add" => CreateScheduleCommand::run(&discord, command).await,
+                "show" => GetScheduleCommand::run(&discord, command).await,
                 _ => NotFoundCommand::run(&discord, command).await
             }
+            "undo" => UndoCommand::run(&discord, command).await,
             _ => NotFoundCommand::run(&discord, command).await
         };
 
         // handle result
         if let Some(message) = result.create_embed() {
             let builder = CreateInteractionResponse::Message(message);
-            if let Err(why) = command.create_response(&ctx.http, builder).await {
+            if let Err(why) = discord.api.create_response(&command.into(), builder).await {
                 println!("API resposne 에러 발생 - {}", why);
             };
         }
     }
 
-    async fn handle_component_interaction(&self, ctx: &Context, component: &ComponentInteraction) {
-
-        // validation
-        let message_interaction = match &component.message.interaction {
+    async fn handle_autocomplete_interaction(&self, ctx: &Context, command: &CommandInteraction) {
+        let guild_id = match command.guild_id {
             Some(v) => v,
-            _ => {
-                print!("message interaction 이 외에 핸들링 필요");
-                return;
-            }
+            _ => return
         };
+        let data = &command.data;
+        let option = data.options.first().map(|option| option.name.as_str()).unwrap_or_default();
+        let actor = Actor { user_id: command.user.id, command: format!("/{} {}", data.name, option) };
+        let discord = Discord::new(self.api(ctx), guild_id, self.database.clone(), self.backup.clone(), actor);
+
+        let result = match (data.name.as_str(), option) {
+            ("db", "restore") => RestoreDBCommand::autocomplete(&discord, command).await,
+            ("todo", "edit") => EditTodoCommand::autocomplete(&discord, command).await,
+            ("todo", "delete") => DeleteTodoCommand::autocomplete(&discord, command).await,
+            ("todo", "done") => DoneTodoCommand::autocomplete(&discord, command).await,
+            ("todo", "checklist") => ChecklistTodoCommand::autocomplete(&discord, command).await,
+            _ => return
+        };
+
+        match result {
+            Ok(response) => {
+                let builder = CreateInteractionResponse::Autocomplete(response);
+                if let Err(why) = discord.api.create_response(&command.into(), builder).await {
+                    println!("API resposne 에러 발생 - {}", why);
+                };
+            }
+            Err(why) => println!("자동완성 에러 발생 - {}", why)
+        }
+    }
+
+    async fn handle_component_interaction(&self, ctx: &Context, component: &ComponentInteraction) {
         // create discord
         let guild_id = match component.guild_id.ok_or_else(|| Error::other("guild id를 찾을 수 없습니다")) {
             Ok(v) => v,
             _ => return
         };
-        let discord = Discord::new(ctx.clone(), guild_id);
+        // 버튼을 단 메세지를 만든 명령어 ex. `todo add`
+        #[allow(deprecated)]
+        let interaction_name = component.message.interaction.as_ref()
+            .map(|interaction| interaction.name.clone())
+            .unwrap_or_default();
+        let custom_id = component.data.custom_id.as_str();
+        let actor = Actor { user_id: component.user.id, command: format!("/{}", interaction_name) };
+        let discord = Discord::new(self.api(ctx), guild_id, self.database.clone(), self.backup.clone(), actor);
 
-        // handle message interaction
-        let interaction_name = message_interaction.name.as_str();
-        let result = match interaction_name {
-            "TODO add" => CreateTodoComponent::run(&discord, component).await,
-            "TODO show" => ShowTodosComponent::run(&discord, component).await,
-            _ => NotFountComponent::run(&discord, component).await
+        // custom id prefix로 먼저 찾고, 없으면 메세지를 만든 명령어로 찾음
+        let result = if custom_id.starts_with(TODO_STATUS_PREFIX) {
+            TodoStatusComponent::run(&discord, component).await
+        } else if custom_id.starts_with(TODO_ASSIGN_PREFIX) {
+            AssignTodoComponent::run(&discord, component).await
+        } else if custom_id.starts_with(TODO_CHECK_PREFIX) {
+            CheckSubtaskComponent::run(&discord, component).await
+        } else if custom_id.starts_with(PAGE_PREFIX) {
+            PageComponent::run(&discord, component).await
+        } else {
+            match interaction_name.as_str() {
+                "todo add" => CreateTodoComponent::run(&discord, component).await,
+                "todo show" => ShowTodosComponent::run(&discord, component).await,
+                // 확인 버튼은 명령어에서 직접 기다림
+                "db force-import" => Ok(None),
+                _ => NotFountComponent::run(&discord, component).await
+            }
         };
 
         // handle result
         if let Some(message) = result.create_embed() {
             let builder = CreateInteractionResponse::Message(message);
-            if let Err(why) = component.create_response(&ctx.http, builder).await {
+            if let Err(why) = discord.api.create_response(&component.into(), builder).await {
                 println!("API resposne 에러 발생 - {}", why);
             };
         }
@@ -156,6 +240,27 @@ impl EventHandler for Handler {
         }
     }
 
+    // 봇이 직접 쓴 변경은 캐시에 이미 반영되어 있으므로 무시
+    async fn message_update(&self, ctx: Context, _old: Option<Message>, _new: Option<Message>, event: MessageUpdateEvent) {
+        if event.author.as_ref().is_some_and(|author| author.id == ctx.cache.current_user().id) {
+            return;
+        }
+        if let Some(guild_id) = event.guild_id {
+            if self.database.is_database_channel(guild_id, event.channel_id) {
+                self.database.invalidate(guild_id);
+            }
+        }
+    }
+
+    // 삭제 이벤트에는 작성자가 없음, 봇이 조각을 줄이며 지운 경우에도 캐시를 버리지만 드묾
+    async fn message_delete(&self, _ctx: Context, channel_id: ChannelId, _deleted_message_id: MessageId, guild_id: Option<GuildId>) {
+        if let Some(guild_id) = guild_id {
+            if self.database.is_database_channel(guild_id, channel_id) {
+                self.database.invalidate(guild_id);
+            }
+        }
+    }
+
     async fn ready(&self, ctx: Context, ready: Ready) {
         println!("{} 봇 실행 완료!", ready.user.name);
 
@@ -169,6 +274,9 @@ impl EventHandler for Handler {
                 .description("투두~")
                 .add_option(
                     CreateCommandOption::new(CommandOptionType::SubCommand, "add", "투두 추가")
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::Boolean, "thread", "논의용 스레드 만들기")
+                        )
                 )
                 .add_option(
                     CreateCommandOption::new(CommandOptionType::SubCommand, "reset", "투두 초기화")
@@ -178,9 +286,110 @@ impl EventHandler for Handler {
                 )
                 .add_option(
                     CreateCommandOption::new(CommandOptionType::SubCommand, "show-all", "전체 투두 확인")
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "team", "이 팀 투두만 ex. iOS")
+                                .max_length(20)
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "label", "이 라벨이 붙은 투두만")
+                                .max_length(20)
+                        )
+                        .add_sub_option(priority_option("priority", "이 중요도 이상인 투두만"))
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::User, "assignee", "이 담당자의 투두만")
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "search", "내용에 이 글자가 포함된 투두만")
+                                .max_length(20)
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "from", "이 날짜부터 마감 ex. 3/1, 이번주 월요일")
+                                .max_length(20)
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "to", "이 날짜까지 마감 ex. 3/31, 월말")
+                                .max_length(20)
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::Boolean, "overdue", "마감이 지난 투두만")
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::Boolean, "this-week", "이번주 마감인 투두만")
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "sort", "정렬 기준, 기본은 마감기한 순")
+                                .add_string_choice("마감기한", "deadline")
+                                .add_string_choice("중요도", "priority")
+                                .add_string_choice("추가한 순", "created")
+                        )
                 )
                 .add_option(
                     CreateCommandOption::new(CommandOptionType::SubCommand, "remind", "리마인드")
+                )
+                .add_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "edit", "투두 수정")
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::Integer, "todo", "수정할 투두")
+                                .required(true)
+                                .set_autocomplete(true)
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "content", "새 내용")
+                                .max_length(300)
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "deadline", "새 마감기한 ex. 3/2, 내일 오후 3시, 다음주 금요일 18:00")
+                        )
+                        .add_sub_option(priority_option("priority", "새 중요도"))
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "labels", "새 라벨, 쉼표로 구분 ex. bug, design")
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "repeat", "반복")
+                                .add_string_choice("반복 안 함", "none")
+                                .add_string_choice("매주", "weekly")
+                                .add_string_choice("매달", "monthly")
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::Integer, "weeks", "매주 반복할 때 몇 주마다, 기본 1")
+                                .min_int_value(1)
+                                .max_int_value(12)
+                        )
+                )
+                .add_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "delete", "투두 삭제")
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::Integer, "todo", "삭제할 투두")
+                                .required(true)
+                                .set_autocomplete(true)
+                        )
+                )
+                .add_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "done", "투두 완료")
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::Integer, "todo", "완료한 투두")
+                                .required(true)
+                                .set_autocomplete(true)
+                        )
+                )
+                .add_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "mine", "내가 맡은 투두")
+                )
+                .add_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "checklist", "투두 체크리스트")
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::Integer, "todo", "체크리스트를 볼 투두")
+                                .required(true)
+                                .set_autocomplete(true)
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "add", "추가할 하위 작업")
+                                .max_length(100)
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::Integer, "remove", "삭제할 하위 작업 번호")
+                                .min_int_value(1)
+                        )
                 ),
             CreateCommand::new("설정")
                 .description("설정~")
@@ -190,9 +399,34 @@ impl EventHandler for Handler {
                             CreateCommandOption::new(CommandOptionType::Channel, "channel", "메인 채널")
                                 .required(true)
                         )
+                )
+                .add_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "log-channel", "변경 기록 채널 설정")
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::Channel, "channel", "변경 기록 채널, 비우면 해제")
+                        )
+                )
+                .add_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "timezone", "마감기한을 계산할 시간대 설정")
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "timezone", "ex. Asia/Seoul, America/New_York")
+                                .required(true)
+                                .max_length(50)
+                        )
+                )
+                .add_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "team-channel", "투두 스레드를 만들 팀 채널 설정")
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "team", "팀 이름 ex. iOS")
+                                .required(true)
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::Channel, "channel", "팀 채널, 비우면 해제")
+                        )
                 ),
             CreateCommand::new("db")
                 .description("데이터베이스 설정")
+                .default_member_permissions(Permissions::ADMINISTRATOR)
                 .add_option(
                     CreateCommandOption::new(CommandOptionType::SubCommand, "reset", "DB 전체 초기화")
                 )
@@ -200,14 +434,52 @@ impl EventHandler for Handler {
                     CreateCommandOption::new(CommandOptionType::SubCommand, "force-import", "DB 강제 불러오기")
                         .add_sub_option(
                             CreateCommandOption::new(CommandOptionType::String, "json", "json raw value")
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::Attachment, "file", "json 파일")
+                        )
+                )
+                .add_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "export", "DB 내보내기")
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::Boolean, "pretty", "json 들여쓰기 여부")
+                        )
+                )
+                .add_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "restore", "DB 백업에서 복원")
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "snapshot", "스냅샷")
                                 .required(true)
+                                .set_autocomplete(true)
+                        )
+                )
+                .add_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "history", "DB 변경 기록")
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::Integer, "page", "페이지")
+                                .min_int_value(1)
                         )
                 ),
             CreateCommand::new("일정")
                 .description("일정 관리")
                 .add_option(
                     CreateCommandOption::new(CommandOptionType::SubCommand, "add", "일정 추가")
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "content", "일정 내용")
+                                .required(true)
+                                .max_length(300)
+                        )
+                        .add_sub_option(
+                            CreateCommandOption::new(CommandOptionType::String, "deadline", "날짜 ex. 3/2, 다음주 금요일 18:00")
+                                .required(true)
+                                .max_length(30)
+                        )
+                )
+                .add_option(
+                    CreateCommandOption::new(CommandOptionType::SubCommand, "show", "일정 확인")
                 ),
+            CreateCommand::new("undo")
+                .description("마지막 변경 되돌리기"),
         ])
             .await
             .expect("명령 생성에 실패했습니다.");
@@ -217,27 +489,45 @@ impl EventHandler for Handler {
         match interaction {
             Interaction::Command(command) => self.handle_command_interaction(&ctx, &command).await,
             Interaction::Component(component) => self.handle_component_interaction(&ctx, &component).await,
+            Interaction::Autocomplete(command) => self.handle_autocomplete_interaction(&ctx, &command).await,
             _ => return,
         };
     }
 }
 
+// 중요도 선택지, 값은 TodoPriority::parse와 같음
+fn priority_option(name: &str, description: &str) -> CreateCommandOption {
+    CreateCommandOption::new(CommandOptionType::String, name, description)
+        .add_string_choice("낮음", "low")
+        .add_string_choice("보통", "normal")
+        .add_string_choice("높음", "high")
+        .add_string_choice("긴급", "urgent")
+}
+
 #[tokio::main]
 async fn main() -> Result<(), JobSchedulerError> {
-    let intents = GatewayIntents::GUILD_MESSAGES
+    let intents = GatewayIntents::GUILDS
+        | GatewayIntents::GUILD_MESSAGES
         | GatewayIntents::MESSAGE_CONTENT
         | GatewayIntents::DIRECT_MESSAGES;
 
-    let config = Config::new();
+    let config = match Config::new() {
+        Ok(v) => v,
+        Err(why) => {
+            println!("설정 Err {}", why);
+            return Ok(())
+        }
+    };
+    let database = create_database(&config);
     let mut client = Client::builder(config.discord_bot_token, intents)
-        .event_handler(Handler)
+        .event_handler(Handler { database: database.clone(), backup: config.backup.clone() })
         .await
         .expect("클라이언트 생성에 실패했습니다.");
 
-    let http = client.http.clone();
-    let mut sched = JobScheduler::new().await?;
+    let api: Arc<dyn DiscordApi> = Arc::new(SerenityApi::new(client.http.clone(), None));
+    let sched = JobScheduler::new().await?;
 
-    match schedule_task(http).await {
+    match schedule_task(api.clone(), database.clone()).await {
         Ok(job) => {
             sched.add(job).await?;
             println!("스케쥴링 Ok");
@@ -248,6 +538,28 @@ async fn main() -> Result<(), JobSchedulerError> {
         }
     };
 
+    match recurrence_task(api.clone(), database.clone()).await {
+        Ok(job) => {
+            sched.add(job).await?;
+            println!("반복 투두 스케쥴링 Ok");
+        }
+        Err(why) => {
+            println!("반복 투두 스케쥴링 Err {}", why);
+            return Ok(())
+        }
+    };
+
+    match backup_task(api, database, config.backup).await {
+        Ok(job) => {
+            sched.add(job).await?;
+            println!("백업 스케쥴링 Ok");
+        }
+        Err(why) => {
+            println!("백업 스케쥴링 Err {}", why);
+            return Ok(())
+        }
+    };
+
     match sched.start().await {
         Err(why) => println!("스케쥴링 Start Err{}", why),
         _ => println!("스케쥴링 Start Ok")
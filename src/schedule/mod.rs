@@ -1,16 +1,19 @@
-use std::str::FromStr;
 use std::sync::Arc;
 use chrono::Utc;
-use serenity::all::{Http};
-use tokio::time::{Instant, sleep_until};
-use tokio_cron_scheduler::{Job, JobBuilder, JobScheduler, JobSchedulerError};
+use serenity::all::CreateMessage;
+use tokio_cron_scheduler::{Job, JobBuilder, JobSchedulerError};
+use crate::config::config::BackupConfig;
+use crate::database::backup_repo::BackupRepo;
+use crate::database::database::DatabaseTrait;
 use crate::database::meta_data_repo::MetaDataRepo;
 use crate::database::team_repo::TeamRepo;
 use crate::database::todo_repo::TodoRepo;
 use crate::entity::todo::{Todo, VecTodoExtension};
 use crate::global::discord::Guild;
+use crate::global::discord_api::DiscordApi;
+use crate::util::page::{paginate, MESSAGE_CONTENT_LIMIT};
 
-pub async fn schedule_task(http: Arc<Http>) -> Result<Job, JobSchedulerError> {
+pub async fn schedule_task(api: Arc<dyn DiscordApi>, database: Arc<dyn DatabaseTrait>) -> Result<Job, JobSchedulerError> {
     JobBuilder::new()
         .with_timezone(chrono_tz::Asia::Seoul)
         .with_cron_job_type()
@@ -18,12 +21,13 @@ pub async fn schedule_task(http: Arc<Http>) -> Result<Job, JobSchedulerError> {
         .unwrap()
         .with_run_async(
             Box::new(move |uuid, mut l| {
-                let http = http.clone();
+                let api = api.clone();
+                let database = database.clone();
                 Box::pin(async move {
                     let next_tick = l.next_tick_for_job(uuid).await;
                     match next_tick {
                         Ok(Some(_)) => {
-                            _ = g(&http).await;
+                            _ = g(&api, &database).await;
                         }
                         _ => println!("Could not get next tick for 7s job"),
                     }
@@ -33,13 +37,84 @@ pub async fn schedule_task(http: Arc<Http>) -> Result<Job, JobSchedulerError> {
         .build()
 }
 
-async fn g(http: &Arc<Http>) -> serenity::Result<()> {
-    let guilds = http.get_guilds(None, None).await?;
+pub async fn backup_task(api: Arc<dyn DiscordApi>, database: Arc<dyn DatabaseTrait>, config: BackupConfig) -> Result<Job, JobSchedulerError> {
+    JobBuilder::new()
+        .with_timezone(chrono_tz::Asia::Seoul)
+        .with_cron_job_type()
+        .with_schedule("0 0 4 * * * *") // 매일 새벽 4시
+        .unwrap()
+        .with_run_async(
+            Box::new(move |_uuid, _l| {
+                let api = api.clone();
+                let database = database.clone();
+                let config = config.clone();
+                Box::pin(async move {
+                    if let Err(why) = backup(&api, &database, &config).await {
+                        println!("백업 실패 {}", why);
+                    }
+                })
+            })
+        )
+        .build()
+}
+
+pub async fn recurrence_task(api: Arc<dyn DiscordApi>, database: Arc<dyn DatabaseTrait>) -> Result<Job, JobSchedulerError> {
+    JobBuilder::new()
+        .with_timezone(chrono_tz::Asia::Seoul)
+        .with_cron_job_type()
+        .with_schedule("0 0 8 * * * *") // 매일 아침 8시, 월요일 스프린트 공지 전
+        .unwrap()
+        .with_run_async(
+            Box::new(move |_uuid, _l| {
+                let api = api.clone();
+                let database = database.clone();
+                Box::pin(async move {
+                    if let Err(why) = regenerate(&api, &database).await {
+                        println!("반복 투두 생성 실패 {}", why);
+                    }
+                })
+            })
+        )
+        .build()
+}
+
+async fn regenerate(api: &Arc<dyn DiscordApi>, database: &Arc<dyn DatabaseTrait>) -> serenity::Result<()> {
+    let guild_ids = api.get_guilds().await?;
+    let now = Utc::now();
+
+    for guild_id in guild_ids {
+        let todo_repo = TodoRepo::new(Guild::new(api.clone(), guild_id), database.clone());
+        match todo_repo.regenerate_recurring(now).await {
+            Ok(0) => {}
+            Ok(count) => println!("{} 반복 투두 {}개 생성", guild_id, count),
+            Err(why) => println!("{} 반복 투두 생성 실패 {}", guild_id, why),
+        }
+    }
+
+    Ok(())
+}
+
+async fn backup(api: &Arc<dyn DiscordApi>, database: &Arc<dyn DatabaseTrait>, config: &BackupConfig) -> serenity::Result<()> {
+    let guild_ids = api.get_guilds().await?;
+
+    for guild_id in guild_ids {
+        let guild = Guild::new(api.clone(), guild_id);
+        let backup_repo = BackupRepo::new(guild.clone(), database.clone(), config);
+        match backup_repo.snapshot().await {
+            Ok(name) => println!("{} 백업 완료 {}", guild.guild_id, name),
+            Err(why) => println!("{} 백업 실패 {}", guild.guild_id, why),
+        }
+    }
+
+    Ok(())
+}
 
-    for guild in guilds {
-        let guild_id = guild.id;
-        let guild = Guild { http: http.clone(), guild_id };
-        let meta_data_repo = MetaDataRepo::new(guild.clone());
+async fn g(api: &Arc<dyn DiscordApi>, database: &Arc<dyn DatabaseTrait>) -> serenity::Result<()> {
+    let guild_ids = api.get_guilds().await?;
+
+    for guild_id in guild_ids {
+        let guild = Guild::new(api.clone(), guild_id);
+        let meta_data_repo = MetaDataRepo::new(guild.clone(), database.clone());
         let channel_id = match meta_data_repo.get_main_channel().await {
             Ok(v) => v,
             Err(why) => {
@@ -47,7 +122,7 @@ async fn g(http: &Arc<Http>) -> serenity::Result<()> {
                 continue
             },
         };
-        let channels = match guild_id.channels(&http).await {
+        let channels = match api.get_channels(guild_id).await {
             Ok(v) => v,
             Err(why) => {
                 println!("channels 불러오기 실패{}", why);
@@ -55,8 +130,8 @@ async fn g(http: &Arc<Http>) -> serenity::Result<()> {
             },
         };
 
-        let team_repo = TeamRepo::new(guild.clone());
-        let todo_repo = TodoRepo::new(guild.clone());
+        let team_repo = TeamRepo::new(guild.clone(), database.clone());
+        let todo_repo = TodoRepo::new(guild.clone(), database.clone());
 
         let teams = team_repo.get_teams().await?;
         let todos = todo_repo.get_todos().await?;
@@ -69,10 +144,88 @@ async fn g(http: &Arc<Http>) -> serenity::Result<()> {
             message.push_str(m.as_str());
         }
 
-        if let Some(channel) = channels.iter().find(|(id, _)| id.get() == channel_id) {
-            channel.0.say(&http, message).await?;
+        // 메세지 길이 제한을 넘으면 여러 메세지로 나눠 보냄
+        if let Some(channel) = channels.iter().find(|channel| channel.id.get() == channel_id) {
+            for content in paginate(&message, MESSAGE_CONTENT_LIMIT) {
+                api.send_message(channel.id, CreateMessage::new().content(content)).await?;
+            }
         }
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::database::database::{Database, DatabaseTrait};
+    use crate::database::meta_data_repo::MetaDataRepo;
+    use crate::entity::todo::{Recurrence, TodoContent, TodoStatus};
+    use crate::global::discord::Guild;
+    use crate::global::discord_api::DiscordApi;
+    use crate::global::fake_discord::{create_test_todo, test_content, FakeDiscord, GUILD_ID};
+    use crate::schedule::{g, regenerate};
+
+    #[tokio::test]
+    async fn posts_sprint_to_main_channel() {
+        let fake = Arc::new(FakeDiscord::new());
+        let api: Arc<dyn DiscordApi> = fake.clone();
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        let main_channel = fake.add_channel("general");
+        let guild = Guild::new(api.clone(), GUILD_ID);
+        MetaDataRepo::new(guild, database.clone()).edit_main_channel(main_channel.get()).await.unwrap();
+        create_test_todo(&fake, &database, "Web", &test_content("랜딩 페이지")).await;
+
+        g(&api, &database).await.unwrap();
+
+        let messages = fake.message_contents(main_channel);
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].starts_with("# 새로운 스프린트가 시작됐습니다!"));
+        assert!(messages[0].contains("## Web\n### - ⬜ <t:1709391599:D> 랜딩 페이지 ⚠️ 마감 지남"));
+        assert!(messages[0].contains("## iOS\n### 할 일이 없네요."));
+    }
+
+    #[tokio::test]
+    async fn splits_long_sprint_message() {
+        let fake = Arc::new(FakeDiscord::new());
+        let api: Arc<dyn DiscordApi> = fake.clone();
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        let main_channel = fake.add_channel("general");
+        let guild = Guild::new(api.clone(), GUILD_ID);
+        MetaDataRepo::new(guild, database.clone()).edit_main_channel(main_channel.get()).await.unwrap();
+        for index in 0..30 {
+            let content = TodoContent { status: TodoStatus::Done, ..test_content(&format!("{index:02} {}", "긴 투두 ".repeat(20))) };
+            create_test_todo(&fake, &database, "Web", &content).await;
+        }
+
+        g(&api, &database).await.unwrap();
+
+        let messages = fake.message_contents(main_channel);
+        assert!(messages.len() > 1);
+        assert!(messages.iter().all(|message| message.chars().count() <= 2000));
+        assert!(messages[0].starts_with("# 새로운 스프린트가 시작됐습니다!"));
+        assert!(messages.concat().contains("29 긴 투두"));
+    }
+
+    #[tokio::test]
+    async fn regenerates_done_recurring_todo() {
+        let fake = Arc::new(FakeDiscord::new());
+        let api: Arc<dyn DiscordApi> = fake.clone();
+        let database: Arc<dyn DatabaseTrait> = Arc::new(Database::default());
+        let content = TodoContent { status: TodoStatus::Done, recurrence: Some(Recurrence::Weekly { weeks: 2 }), ..test_content("릴리즈 노트") };
+        create_test_todo(&fake, &database, "Web", &content).await;
+
+        regenerate(&api, &database).await.unwrap();
+        regenerate(&api, &database).await.unwrap();
+
+        let todos = fake.stored_entity().todos;
+        assert_eq!(todos.len(), 2);
+        assert_eq!(todos[0].todo.recurrence, None);
+        assert_eq!(todos[1].id, 2);
+        assert_eq!(todos[1].todo.status, TodoStatus::Todo);
+        assert_eq!(todos[1].todo.recurrence, Some(Recurrence::Weekly { weeks: 2 }));
+        assert!(!todos[1].todo.deadline.is_over(chrono::Utc::now()));
+        assert_eq!(todos[1].todo.deadline.at.time(), todos[0].todo.deadline.at.time());
+        assert_eq!((todos[1].todo.deadline.at - todos[0].todo.deadline.at).num_days() % 14, 0);
+    }
+}
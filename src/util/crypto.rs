@@ -0,0 +1,151 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use hkdf::Hkdf;
+use sha2::{Digest, Sha256};
+use serenity::{Error, Result};
+
+// 암호화된 payload 앞에 붙는 형식 표시
+const ENCRYPTED_HEADER: char = 'E';
+const KEY_ID_SIZE: usize = 4;
+const NONCE_SIZE: usize = 12;
+// HKDF에 쓰는 고정 값, 바꾸면 이전에 암호화한 데이터를 읽을 수 없음
+const KEY_SALT: &[u8] = b"mowgli-database-salt-v1";
+const KEY_INFO: &[u8] = b"mowgli-database-key-v1";
+
+pub const WRONG_KEY_MESSAGE: &str = "데이터베이스 암호화 키가 맞지 않습니다. 'DATABASE_SECRET' 또는 'DATABASE_OLD_SECRETS'를 확인해주세요";
+const MISSING_KEY_MESSAGE: &str = "암호화된 데이터베이스입니다. 'DATABASE_SECRET'을 설정해주세요";
+
+pub fn is_encrypted(payload: &str) -> bool {
+    payload.starts_with(ENCRYPTED_HEADER)
+}
+
+// 키 문제로 읽지 못한 경우, 데이터가 깨진 것이 아니므로 덮어쓰면 안 됨
+pub fn is_key_error(why: &Error) -> bool {
+    matches!(why, Error::Other(message) if *message == WRONG_KEY_MESSAGE || *message == MISSING_KEY_MESSAGE)
+}
+
+// `E` + base64(key id + nonce + 암호문), key id로 어떤 키로 암호화했는지 구분합니다
+pub fn encrypt(payload: &str, secret: &str) -> Result<String> {
+    let key = derive_key(secret);
+    let cipher = ChaCha20Poly1305::new(&key);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher.encrypt(&nonce, payload.as_bytes())
+        .map_err(|_| Error::Other("데이터베이스를 암호화할 수 없습니다"))?;
+
+    let mut bytes = key_id(&key).to_vec();
+    bytes.extend_from_slice(&nonce);
+    bytes.extend_from_slice(&ciphertext);
+    Ok(format!("{ENCRYPTED_HEADER}{}", STANDARD.encode(bytes)))
+}
+
+// secrets 중 key id가 맞는 키로 복호화합니다, 이전 키로 암호화된 값도 읽을 수 있음
+pub fn decrypt(payload: &str, secrets: &[String]) -> Result<String> {
+    let encoded = payload.strip_prefix(ENCRYPTED_HEADER)
+        .ok_or_else(|| Error::Other("암호화된 데이터베이스가 아닙니다"))?;
+    if secrets.is_empty() {
+        Err(Error::Other(MISSING_KEY_MESSAGE))?
+    }
+
+    let bytes = STANDARD.decode(encoded.trim()).map_err(|_| Error::Other("암호화된 데이터베이스의 base64가 올바르지 않습니다"))?;
+    if bytes.len() < KEY_ID_SIZE + NONCE_SIZE {
+        Err(Error::Other("암호화된 데이터베이스가 손상됐습니다"))?
+    }
+    let (id, rest) = bytes.split_at(KEY_ID_SIZE);
+    let (nonce, ciphertext) = rest.split_at(NONCE_SIZE);
+
+    // HKDF로 바꾸기 전에 sha256으로 만든 키로 암호화된 데이터도 읽음
+    let key = secrets.iter()
+        .flat_map(|secret| [derive_key(secret), legacy_key(secret)])
+        .find(|key| key_id(key) == id)
+        .ok_or_else(|| Error::Other(WRONG_KEY_MESSAGE))?;
+    let plaintext = ChaCha20Poly1305::new(&key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| Error::Other(WRONG_KEY_MESSAGE))?;
+    String::from_utf8(plaintext).map_err(|_| Error::Other("암호화된 데이터베이스가 손상됐습니다"))
+}
+
+// secret을 그대로 해시하지 않고 HKDF-SHA256으로 키를 만듭니다
+fn derive_key(secret: &str) -> Key {
+    let mut key = Key::default();
+    Hkdf::<Sha256>::new(Some(KEY_SALT), secret.as_bytes())
+        .expand(KEY_INFO, &mut key)
+        .expect("32바이트 키는 HKDF 출력 길이 제한보다 짧습니다");
+    key
+}
+
+fn legacy_key(secret: &str) -> Key {
+    let hash = Sha256::digest(secret.as_bytes());
+    *Key::from_slice(&hash)
+}
+
+fn key_id(key: &Key) -> [u8; KEY_ID_SIZE] {
+    let hash = Sha256::digest(key);
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+    use chacha20poly1305::ChaCha20Poly1305;
+
+    use crate::util::crypto::{decrypt, derive_key, encrypt, is_encrypted, is_key_error, key_id, legacy_key, ENCRYPTED_HEADER, WRONG_KEY_MESSAGE};
+
+    const PAYLOAD: &str = r#"{"todos":[]}"#;
+
+    fn secrets(secrets: &[&str]) -> Vec<String> {
+        secrets.iter().map(|secret| secret.to_string()).collect()
+    }
+
+    #[test]
+    fn round_trips() {
+        let encrypted = encrypt(PAYLOAD, "secret").unwrap();
+        assert!(is_encrypted(&encrypted));
+        assert!(!encrypted.contains("todos"));
+        assert_eq!(decrypt(&encrypted, &secrets(&["secret"])).unwrap(), PAYLOAD);
+    }
+
+    #[test]
+    fn reads_with_old_secret() {
+        let encrypted = encrypt(PAYLOAD, "old").unwrap();
+        assert_eq!(decrypt(&encrypted, &secrets(&["new", "old"])).unwrap(), PAYLOAD);
+    }
+
+    #[test]
+    fn rejects_wrong_secret() {
+        let encrypted = encrypt(PAYLOAD, "secret").unwrap();
+        let why = decrypt(&encrypted, &secrets(&["other"])).unwrap_err();
+        assert_eq!(why.to_string(), WRONG_KEY_MESSAGE);
+        assert!(is_key_error(&why));
+    }
+
+    #[test]
+    fn requires_secret() {
+        let encrypted = encrypt(PAYLOAD, "secret").unwrap();
+        assert!(is_key_error(&decrypt(&encrypted, &[]).unwrap_err()));
+    }
+
+    #[test]
+    fn derives_key_with_hkdf() {
+        let key = derive_key("secret");
+        assert_eq!(key, derive_key("secret"));
+        assert_ne!(key, derive_key("other"));
+        assert_ne!(key, legacy_key("secret"));
+    }
+
+    #[test]
+    fn reads_with_legacy_key() {
+        let key = legacy_key("secret");
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = ChaCha20Poly1305::new(&key).encrypt(&nonce, PAYLOAD.as_bytes()).unwrap();
+        let mut bytes = key_id(&key).to_vec();
+        bytes.extend_from_slice(&nonce);
+        bytes.extend_from_slice(&ciphertext);
+        let encrypted = format!("{ENCRYPTED_HEADER}{}", STANDARD.encode(bytes));
+
+        assert_eq!(decrypt(&encrypted, &secrets(&["secret"])).unwrap(), PAYLOAD);
+    }
+}
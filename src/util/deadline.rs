@@ -0,0 +1,259 @@
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use crate::entity::deadline::Deadline;
+
+pub const INVALID_DEADLINE_MESSAGE: &str = "마감일을 제대로 입력해주세요. \nex. 3/2, 2025-03-02, 내일 오후 3시, 다음주 금요일 18:00, 3일 후, 월말";
+
+// timezone 기준 지금
+pub fn now(timezone: Tz) -> DateTime<Tz> {
+    Utc::now().with_timezone(&timezone)
+}
+
+// 마감기한 입력을 now의 시간대 기준 Deadline으로 바꿉니다
+// 날짜 뒤에 시각을 붙일 수 있고(`18:00`, `오후 3시`, `3시 30분`), 시각만 입력하면 오늘
+pub fn parse_deadline(input: &str, now: DateTime<Tz>) -> Option<Deadline> {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let today = now.date_naive();
+    let timezone = now.timezone();
+
+    // 시각은 최대 세 단어 ex. `오후 3시 30분`
+    for count in (1..=words.len().min(3)).rev() {
+        let (date, time) = words.split_at(words.len() - count);
+        let Some(time) = parse_time(&time.join(" ")) else { continue };
+        let date = if date.is_empty() { Some(today) } else { parse_date(&date.join(" "), today) };
+        if let Some(date) = date {
+            return Some(Deadline::date_time(date.and_time(time), timezone));
+        }
+    }
+    parse_date(input, today).map(|date| Deadline::date(date, timezone))
+}
+
+// - `HH:MM`
+// - `N시`, `N시 M분`, `N시 반`
+// - 앞에 `오전`, `오후`를 붙이면 12시간제
+fn parse_time(input: &str) -> Option<NaiveTime> {
+    // 오후면 Some(true)
+    let (is_pm, input) = match (input.strip_prefix("오전"), input.strip_prefix("오후")) {
+        (Some(rest), _) => (Some(false), rest.trim()),
+        (_, Some(rest)) => (Some(true), rest.trim()),
+        _ => (None, input.trim()),
+    };
+
+    let (hour, minute): (u32, u32) = if let Some((hour, minute)) = input.split_once(':') {
+        (hour.parse().ok()?, minute.parse().ok()?)
+    } else {
+        let (hour, minute) = input.split_once('시')?;
+        let minute = match minute.trim() {
+            "" => 0,
+            "반" => 30,
+            minute => minute.strip_suffix('분')?.trim().parse().ok()?,
+        };
+        (hour.trim().parse().ok()?, minute)
+    };
+
+    let hour = match is_pm {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(false) => hour % 12,
+        Some(true) => hour % 12 + 12,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+// 마감 날짜 입력을 today 기준 날짜로 바꿉니다
+// - `YYYY-MM-DD` (`/`, `.` 구분도 가능)
+// - `M/D`, 올해/작년/내년 중 today와 가장 가까운 날짜
+// - 오늘, 내일, 모레, 글피
+// - `N일 후`, `N주 후` (`뒤`도 가능)
+// - `금요일`, `이번주 금요일`, `다음주 금요일`, `다다음주 금요일`
+// - 월말
+pub fn parse_date(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let input: String = input.split_whitespace().collect::<Vec<&str>>().join(" ");
+    let input = input.as_str();
+
+    match input {
+        "오늘" => return Some(today),
+        "내일" => return today.checked_add_days(Days::new(1)),
+        "모레" => return today.checked_add_days(Days::new(2)),
+        "글피" => return today.checked_add_days(Days::new(3)),
+        "월말" => return end_of_month(today),
+        _ => {}
+    }
+
+    parse_full_date(input)
+        .or_else(|| parse_month_day(input, today))
+        .or_else(|| parse_after(input, today))
+        .or_else(|| parse_weekday(input, today))
+}
+
+fn parse_full_date(input: &str) -> Option<NaiveDate> {
+    let parts: Vec<&str> = input.split(['-', '/', '.']).collect();
+    let [year, month, day] = parts.as_slice() else { return None };
+    if year.len() != 4 {
+        return None;
+    }
+    NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)
+}
+
+fn parse_month_day(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (month, day) = input.split_once('/')?;
+    let (month, day): (u32, u32) = (month.trim().parse().ok()?, day.trim().parse().ok()?);
+
+    // 12월에 1/5를 입력하면 내년, 1월에 12/28을 입력하면 작년
+    [today.year() - 1, today.year(), today.year() + 1].into_iter()
+        .filter_map(|year| NaiveDate::from_ymd_opt(year, month, day))
+        .min_by_key(|date| (*date - today).num_days().abs())
+}
+
+fn parse_after(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let amount = input.strip_suffix(" 후")
+        .or_else(|| input.strip_suffix(" 뒤"))
+        .or_else(|| input.strip_suffix('후'))
+        .or_else(|| input.strip_suffix('뒤'))?
+        .trim();
+    if let Some(days) = amount.strip_suffix('일') {
+        return today.checked_add_days(Days::new(days.trim().parse().ok()?));
+    }
+    if let Some(weeks) = amount.strip_suffix('주') {
+        let weeks: u64 = weeks.trim().parse().ok()?;
+        return today.checked_add_days(Days::new(weeks * 7));
+    }
+    None
+}
+
+fn parse_weekday(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let (weeks, weekday) = match input.split_once(' ') {
+        Some(("이번주", weekday)) => (Some(0), weekday),
+        Some(("다음주", weekday)) => (Some(1), weekday),
+        Some(("다다음주", weekday)) => (Some(2), weekday),
+        Some(_) => return None,
+        None => (None, input),
+    };
+    let weekday = parse_weekday_name(weekday)?;
+
+    match weeks {
+        // 주는 월요일부터
+        Some(weeks) => {
+            let monday = today.checked_sub_days(Days::new(today.weekday().num_days_from_monday() as u64))?;
+            monday.checked_add_days(Days::new(weeks * 7 + weekday.num_days_from_monday() as u64))
+        }
+        // 요일만 입력하면 오늘을 포함해 가장 가까운 그 요일
+        None => {
+            let days = (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+            today.checked_add_days(Days::new(days as u64))
+        }
+    }
+}
+
+fn parse_weekday_name(input: &str) -> Option<Weekday> {
+    let name = input.strip_suffix("요일").unwrap_or(input);
+    match name {
+        "월" => Some(Weekday::Mon),
+        "화" => Some(Weekday::Tue),
+        "수" => Some(Weekday::Wed),
+        "목" => Some(Weekday::Thu),
+        "금" => Some(Weekday::Fri),
+        "토" => Some(Weekday::Sat),
+        "일" => Some(Weekday::Sun),
+        _ => None
+    }
+}
+
+fn end_of_month(today: NaiveDate) -> Option<NaiveDate> {
+    let first_day = today.with_day(1)?;
+    first_day.checked_add_months(Months::new(1))?.checked_sub_days(Days::new(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, TimeZone};
+    use chrono_tz::Asia::Seoul;
+
+    use crate::entity::deadline::Deadline;
+    use crate::util::deadline::{parse_date, parse_deadline};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    // 2025-03-05 수요일
+    fn today() -> NaiveDate {
+        date(2025, 3, 5)
+    }
+
+    #[test]
+    fn parses_full_date() {
+        assert_eq!(parse_date("2026-01-02", today()), Some(date(2026, 1, 2)));
+        assert_eq!(parse_date("2026/1/2", today()), Some(date(2026, 1, 2)));
+        assert_eq!(parse_date("2026.01.02", today()), Some(date(2026, 1, 2)));
+        assert_eq!(parse_date("2026-02-30", today()), None);
+    }
+
+    #[test]
+    fn parses_month_day_in_current_year() {
+        assert_eq!(parse_date("3/2", today()), Some(date(2025, 3, 2)));
+        assert_eq!(parse_date("4/10", today()), Some(date(2025, 4, 10)));
+        assert_eq!(parse_date("13/1", today()), None);
+    }
+
+    #[test]
+    fn rolls_month_day_over_year() {
+        assert_eq!(parse_date("1/5", date(2025, 12, 28)), Some(date(2026, 1, 5)));
+        assert_eq!(parse_date("12/30", date(2026, 1, 2)), Some(date(2025, 12, 30)));
+    }
+
+    #[test]
+    fn parses_relative_days() {
+        assert_eq!(parse_date("오늘", today()), Some(date(2025, 3, 5)));
+        assert_eq!(parse_date("내일", today()), Some(date(2025, 3, 6)));
+        assert_eq!(parse_date("모레", today()), Some(date(2025, 3, 7)));
+        assert_eq!(parse_date(" 글피 ", today()), Some(date(2025, 3, 8)));
+    }
+
+    #[test]
+    fn parses_after() {
+        assert_eq!(parse_date("3일 후", today()), Some(date(2025, 3, 8)));
+        assert_eq!(parse_date("30일뒤", today()), Some(date(2025, 4, 4)));
+        assert_eq!(parse_date("2주 후", today()), Some(date(2025, 3, 19)));
+        assert_eq!(parse_date("사흘 후", today()), None);
+    }
+
+    #[test]
+    fn parses_weekday() {
+        assert_eq!(parse_date("금요일", today()), Some(date(2025, 3, 7)));
+        assert_eq!(parse_date("수요일", today()), Some(date(2025, 3, 5)));
+        assert_eq!(parse_date("월요일", today()), Some(date(2025, 3, 10)));
+        assert_eq!(parse_date("이번주 월요일", today()), Some(date(2025, 3, 3)));
+        assert_eq!(parse_date("다음주 금요일", today()), Some(date(2025, 3, 14)));
+        assert_eq!(parse_date("다음주 금", today()), Some(date(2025, 3, 14)));
+        assert_eq!(parse_date("다다음주 일요일", today()), Some(date(2025, 3, 23)));
+        assert_eq!(parse_date("지난주 금요일", today()), None);
+    }
+
+    #[test]
+    fn parses_end_of_month() {
+        assert_eq!(parse_date("월말", today()), Some(date(2025, 3, 31)));
+        assert_eq!(parse_date("월말", date(2024, 2, 10)), Some(date(2024, 2, 29)));
+        assert_eq!(parse_date("월말", date(2025, 12, 31)), Some(date(2025, 12, 31)));
+    }
+
+    #[test]
+    fn rejects_unknown() {
+        assert_eq!(parse_date("", today()), None);
+        assert_eq!(parse_date("언젠가", today()), None);
+    }
+
+    #[test]
+    fn parses_deadline_with_time() {
+        let now = Seoul.with_ymd_and_hms(2025, 3, 5, 9, 0, 0).unwrap();
+        let at = |day: u32, hour: u32, minute: u32| Deadline::date_time(date(2025, 3, day).and_hms_opt(hour, minute, 0).unwrap(), Seoul);
+        assert_eq!(parse_deadline("3/7", now), Some(Deadline::date(date(2025, 3, 7), Seoul)));
+        assert_eq!(parse_deadline("3/7 18:00", now), Some(at(7, 18, 0)));
+        assert_eq!(parse_deadline("내일 오후 3시", now), Some(at(6, 15, 0)));
+        assert_eq!(parse_deadline("다음주 금요일 오전 12시 30분", now), Some(at(14, 0, 30)));
+        assert_eq!(parse_deadline("모레 9시 반", now), Some(at(7, 9, 30)));
+        assert_eq!(parse_deadline("21:00", now), Some(at(5, 21, 0)));
+        assert_eq!(parse_deadline("내일 25:00", now), None);
+        assert_eq!(parse_deadline("내일 오후 13시", now), None);
+    }
+}
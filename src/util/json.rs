@@ -1,15 +1,80 @@
+use std::io::{Read, Write};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
 use serde::Serialize;
-use crate::config::config::Config;
+use serenity::{Error, Result};
+use crate::config::config::{Codec, Config};
+
+// 압축된 payload 앞에 붙는 형식 표시, json은 항상 '{'로 시작하므로 겹치지 않음
+const DEFLATE_HEADER: char = 'D';
 
-pub fn to_string<T>(value: &T) -> serenity::Result<String>
+pub fn to_string<T>(value: &T) -> Result<String>
 where
     T: ?Sized + Serialize,
 {
-    let is_json_pretty = Config::new().is_json_pretty;
+    let is_json_pretty = Config::is_json_pretty();
     let result = if is_json_pretty {
         serenity::json::to_string_pretty(value)?
     } else {
         serenity::json::to_string(value)?
     };
     Ok(result)
-}
\ No newline at end of file
+}
+
+// 저장할 json을 codec 형식으로 바꿉니다
+pub fn encode(json: &str, codec: Codec) -> Result<String> {
+    match codec {
+        Codec::Json => Ok(json.to_string()),
+        Codec::Deflate => {
+            let mut encoder = DeflateEncoder::new(vec![], Compression::best());
+            encoder.write_all(json.as_bytes())?;
+            let bytes = encoder.finish()?;
+            Ok(format!("{DEFLATE_HEADER}{}", STANDARD.encode(bytes)))
+        }
+    }
+}
+
+// 형식 표시를 보고 json으로 되돌립니다, 표시가 없으면 이전에 저장된 json
+pub fn decode(payload: &str) -> Result<String> {
+    let Some(encoded) = payload.strip_prefix(DEFLATE_HEADER) else {
+        return Ok(payload.to_string());
+    };
+    let bytes = STANDARD.decode(encoded.trim()).map_err(|_| Error::Other("압축된 데이터베이스의 base64가 올바르지 않습니다"))?;
+    let mut json = String::new();
+    DeflateDecoder::new(bytes.as_slice())
+        .read_to_string(&mut json)
+        .map_err(|_| Error::Other("압축된 데이터베이스를 풀 수 없습니다"))?;
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::config::Codec;
+    use crate::util::json::{decode, encode};
+
+    const JSON: &str = r#"{"teams":[{"name":"iOS"},{"name":"iOS"},{"name":"iOS"},{"name":"iOS"}]}"#;
+
+    #[test]
+    fn keeps_plain_json() {
+        assert_eq!(encode(JSON, Codec::Json).unwrap(), JSON);
+        assert_eq!(decode(JSON).unwrap(), JSON);
+    }
+
+    #[test]
+    fn round_trips_deflate() {
+        let payload = encode(JSON, Codec::Deflate).unwrap();
+        assert!(payload.starts_with('D'));
+        assert!(payload.len() < JSON.len());
+        assert_eq!(decode(&payload).unwrap(), JSON);
+    }
+
+    #[test]
+    fn rejects_broken_payload() {
+        assert!(decode("D!!!").is_err());
+        assert!(decode("DAAAA").is_err());
+    }
+}
@@ -1,4 +1,7 @@
 pub mod create_embed_extension;
 
 pub mod json;
+pub mod crypto;
 pub mod colour;
+pub mod deadline;
+pub mod page;
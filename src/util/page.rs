@@ -0,0 +1,137 @@
+use serenity::all::{ButtonStyle, CreateActionRow, CreateButton, CreateEmbed, CreateEmbedFooter, CreateInteractionResponseMessage};
+
+use crate::util::colour::GREEN;
+
+// 디스코드 길이 제한
+pub const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+pub const MESSAGE_CONTENT_LIMIT: usize = 2000;
+
+// 버튼 custom id `page:{page}:{state}`, state는 같은 목록을 다시 그리는 데 필요한 값
+pub const PAGE_PREFIX: &str = "page:";
+// custom id 최대 길이
+const MAX_CUSTOM_ID: usize = 100;
+
+// 줄 단위로 limit 글자 이하씩 나눕니다, limit보다 긴 줄은 중간에서 자름
+pub fn paginate(text: &str, limit: usize) -> Vec<String> {
+    let mut pages: Vec<String> = vec![];
+    let mut page = String::new();
+    let mut page_len = 0;
+    for line in text.split_inclusive('\n') {
+        let line_len = line.chars().count();
+        if page_len + line_len > limit && !page.is_empty() {
+            pages.push(std::mem::take(&mut page));
+            page_len = 0;
+        }
+        if line_len > limit {
+            let chars: Vec<char> = line.chars().collect();
+            let mut chunks = chars.chunks(limit).map(|chunk| chunk.iter().collect::<String>()).peekable();
+            while let Some(chunk) = chunks.next() {
+                if chunks.peek().is_some() {
+                    pages.push(chunk);
+                } else {
+                    page_len = chunk.chars().count();
+                    page = chunk;
+                }
+            }
+            continue;
+        }
+        page.push_str(line);
+        page_len += line_len;
+    }
+    if !page.is_empty() || pages.is_empty() {
+        pages.push(page);
+    }
+    pages
+}
+
+// `page:{page}:{state}`에서 page와 state
+pub fn parse_page(custom_id: &str) -> Option<(usize, &str)> {
+    let (page, state) = custom_id.strip_prefix(PAGE_PREFIX)?.split_once(':')?;
+    Some((page.parse().ok()?, state))
+}
+
+// pages 중 page번째 embed, 여러 장이면 이전/다음 버튼과 쪽수를 붙입니다
+pub fn page_message(pages: &[String], page: usize, state: &str) -> CreateInteractionResponseMessage {
+    let (create_embed, buttons) = page_embed(pages, page, state);
+    let message = CreateInteractionResponseMessage::new().add_embed(create_embed);
+    match buttons {
+        Some(buttons) => message.components(vec![buttons]),
+        None => message
+    }
+}
+
+// 다른 버튼과 함께 달 수 있도록 embed와 이전/다음 버튼 줄을 따로 돌려줍니다
+pub fn page_embed(pages: &[String], page: usize, state: &str) -> (CreateEmbed, Option<CreateActionRow>) {
+    let page = page.min(pages.len().saturating_sub(1));
+    let create_embed = CreateEmbed::new()
+        .description(pages.get(page).cloned().unwrap_or_default())
+        .color(GREEN);
+    if pages.len() <= 1 {
+        return (create_embed, None);
+    }
+
+    let footer = format!("{} / {}", page + 1, pages.len());
+    // state가 잘리면 다른 목록이 되므로 버튼을 달지 않음
+    if format!("{PAGE_PREFIX}{}:{state}", pages.len()).chars().count() > MAX_CUSTOM_ID {
+        let create_embed = create_embed.footer(CreateEmbedFooter::new(format!("{footer} · 조건이 길어 페이지를 넘길 수 없습니다")));
+        return (create_embed, None);
+    }
+
+    let custom_id = |page: usize| format!("{PAGE_PREFIX}{page}:{state}");
+    // 첫 장, 마지막 장에서도 custom id가 겹치지 않도록 누를 수 없는 버튼은 자기 쪽을 가리킴
+    let previous = CreateButton::new(custom_id(page.saturating_sub(1)))
+        .label("◀ 이전")
+        .style(ButtonStyle::Secondary)
+        .disabled(page == 0);
+    let next = CreateButton::new(custom_id((page + 1).min(pages.len() - 1)))
+        .label("다음 ▶")
+        .style(ButtonStyle::Secondary)
+        .disabled(page + 1 == pages.len());
+    (create_embed.footer(CreateEmbedFooter::new(footer)), Some(CreateActionRow::Buttons(vec![previous, next])))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::util::page::{page_message, paginate, parse_page};
+
+    #[test]
+    fn keeps_short_text() {
+        assert_eq!(paginate("a\nb\n", 10), vec!["a\nb\n"]);
+        assert_eq!(paginate("", 10), vec![""]);
+    }
+
+    #[test]
+    fn splits_on_lines() {
+        assert_eq!(paginate("aaaa\nbbbb\ncccc\n", 10), vec!["aaaa\nbbbb\n", "cccc\n"]);
+        assert!(paginate(&"투두\n".repeat(3000), 4096).iter().all(|page| page.chars().count() <= 4096));
+    }
+
+    #[test]
+    fn splits_long_line() {
+        assert_eq!(paginate("ab\nccccccccccccc\nd", 5), vec!["ab\n", "ccccc", "ccccc", "ccc\nd"]);
+    }
+
+    #[test]
+    fn parses_page() {
+        assert_eq!(parse_page("page:2:l=bug;p=high;"), Some((2, "l=bug;p=high;")));
+        assert_eq!(parse_page("page:x:"), None);
+        assert_eq!(parse_page("todo-status:1"), None);
+    }
+
+    #[test]
+    fn adds_buttons_only_for_multiple_pages() {
+        let pages = vec![String::from("a"), String::from("b"), String::from("c")];
+        let message = serenity::json::to_value(page_message(&pages, 0, "s")).unwrap();
+        let buttons = &message["components"][0]["components"];
+        assert_eq!(buttons[0]["custom_id"], "page:0:s");
+        assert_eq!(buttons[0]["disabled"], true);
+        assert_eq!(buttons[1]["custom_id"], "page:1:s");
+        assert_eq!(message["embeds"][0]["footer"]["text"], "1 / 3");
+
+        let message = serenity::json::to_value(page_message(&pages[..1], 0, "s")).unwrap();
+        assert!(message.get("components").is_none());
+
+        let message = serenity::json::to_value(page_message(&pages, 0, &"s".repeat(100))).unwrap();
+        assert!(message.get("components").is_none());
+    }
+}